@@ -0,0 +1,73 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Indices of the examples of a day that have at least one expected answer
+/// in `data/{year}/{day}.examples.toml`.
+fn answered_examples(sidecar: &Path) -> Vec<i64> {
+    let Ok(content) = fs::read_to_string(sidecar) else {
+        return Vec::new();
+    };
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let examples = table.get("example").and_then(|e| e.as_array());
+    examples
+        .into_iter()
+        .flatten()
+        .filter(|e| e.get("part1").is_some() || e.get("part2").is_some())
+        .filter_map(|e| e.get("index")?.as_integer())
+        .collect()
+}
+
+/// Turns every `data/{year}/{day}.example{N}.txt` into its own test case
+/// calling `check_example(year, day, N)`, see `days::tests`. Examples
+/// without an expected answer yet are generated as ignored tests.
+///
+/// The whole `data` folder is watched, so adding the first example of a day
+/// regenerates the tests too.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=data");
+
+    let mut examples = Vec::new();
+    for year in fs::read_dir("data").into_iter().flatten().flatten() {
+        let Some(year_number) = year
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<i32>().ok())
+        else {
+            continue;
+        };
+        for file in fs::read_dir(year.path()).into_iter().flatten().flatten() {
+            let name = file.file_name().to_string_lossy().to_string();
+            let parsed = name
+                .strip_suffix(".txt")
+                .and_then(|name| name.split_once(".example"))
+                .and_then(|(day, index)| {
+                    Some((day.parse::<i32>().ok()?, index.parse::<usize>().ok()?))
+                });
+            if let Some((day, index)) = parsed {
+                let sidecar = year.path().join(format!("{}.examples.toml", day));
+                let answered = answered_examples(&sidecar).contains(&(index as i64));
+                examples.push((year_number, day, index, answered));
+            }
+        }
+    }
+    examples.sort();
+
+    let tests: String = examples
+        .iter()
+        .map(|(year, day, index, answered)| {
+            let ignore = match answered {
+                true => "",
+                false => "#[ignore = \"no expected answers in the examples.toml sidecar\"]\n",
+            };
+            format!(
+                "#[test]\n{ignore}fn y{year}_day{day:02}_example{index}() -> anyhow::Result<()> {{\n    check_example({year}, {day}, {index})\n}}\n\n"
+            )
+        })
+        .collect();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
@@ -1,12 +1,38 @@
 use std::fmt::Display;
-use std::fs;
-use std::io;
+use std::io::{self, Read};
+use std::path::PathBuf;
 use std::str::FromStr;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context, Result};
-use log::{debug, info};
-use reqwest::{blocking::ClientBuilder, cookie::Jar, Url};
+use log::{debug, error, info};
+
+pub mod alloc;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod budget;
+pub mod client;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod input;
+pub mod isolate;
+pub mod ledger;
+pub mod parse_error;
+pub mod report;
+pub mod submit;
+#[cfg(test)]
+mod test_server;
+pub mod toml_file;
+pub mod unlock;
+pub mod word_search;
+
+use alloc::{measure, AllocStats};
+use client::AocClient;
+pub use fetch::fetch_input;
+use isolate::{run_isolated, PartFailure};
 
 pub type AOCYearDay = (i32, i32);
 
@@ -14,16 +40,164 @@ pub trait Day<T: FromStr + Sized = Self> {
     type DayOutputPart1: Display;
     type DayOutputPart2: Display;
 
-    fn part1(self) -> Result<Self::DayOutputPart1>;
-    fn part2(self) -> Result<Self::DayOutputPart2>;
+    /// Puzzle title as shown on the AoC page, e.g. `Day 1: Historian Hysteria`
+    const TITLE: &'static str;
+
+    /// Longest time parsing, preparing and solving both parts may take,
+    /// days without one only count towards the budget of their year
+    const BUDGET: Option<Duration> = None;
+
+    /// State derived from the parsed input once and shared by both parts,
+    /// `()` when the parts need nothing more than the input itself
+    type Prepared;
+
+    fn prepare(&self) -> Result<Self::Prepared>;
+    fn part1(&self, prepared: &Self::Prepared) -> Result<Self::DayOutputPart1>;
+    fn part2(&self, prepared: &Self::Prepared) -> Result<Self::DayOutputPart2>;
     fn id() -> AOCYearDay;
+
+    /// Days where part 2 builds on the work done for part 1 can solve both
+    /// in one go, `None` falls back to solving the parts separately.
+    fn solve_both(
+        &self,
+        _prepared: &Self::Prepared,
+    ) -> Option<Result<(Self::DayOutputPart1, Self::DayOutputPart2)>> {
+        None
+    }
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DayPart {
+    #[value(name = "1")]
     Part1,
+    #[value(name = "2")]
     Part2,
 }
 
+impl DayPart {
+    /// Level number as AoC expects it in the answer form
+    pub fn level(&self) -> u8 {
+        match self {
+            DayPart::Part1 => 1,
+            DayPart::Part2 => 2,
+        }
+    }
+}
+
+/// Answer for one part and how long it took to solve
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub answer: String,
+    pub elapsed: Duration,
+    /// Heap usage, only tracked with the `alloc-stats` feature
+    pub alloc: Option<AllocStats>,
+    /// Set when the part panicked or timed out, the answer is empty then
+    pub failure: Option<PartFailure>,
+}
+
+impl PartResult {
+    fn failed(failure: PartFailure, elapsed: Duration) -> Self {
+        PartResult {
+            answer: String::new(),
+            elapsed,
+            alloc: None,
+            failure: Some(failure),
+        }
+    }
+}
+
+/// Answers and timings produced by `process_day`, parts that were not
+/// requested are left out.
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub id: AOCYearDay,
+    pub parse_time: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub prepare_time: Duration,
+    pub prepare_alloc: Option<AllocStats>,
+    pub part1: Option<PartResult>,
+    pub part2: Option<PartResult>,
+}
+
+impl DayResult {
+    pub fn part(&self, part: DayPart) -> Option<&PartResult> {
+        match part {
+            DayPart::Part1 => self.part1.as_ref(),
+            DayPart::Part2 => self.part2.as_ref(),
+        }
+    }
+
+    /// Answer of a part that ran to completion
+    pub fn answer(&self, part: DayPart) -> Option<&str> {
+        self.part(part)
+            .filter(|p| p.failure.is_none())
+            .map(|p| p.answer.as_str())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &PartFailure> {
+        [&self.part1, &self.part2]
+            .into_iter()
+            .filter_map(|p| p.as_ref()?.failure.as_ref())
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time
+            + self.prepare_time
+            + [&self.part1, &self.part2]
+                .iter()
+                .filter_map(|p| p.as_ref())
+                .map(|p| p.elapsed)
+                .sum::<Duration>()
+    }
+}
+
+/// Where the puzzle input comes from when not using the cached AoC input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for InputSource {
+    /// `-` stands for stdin, anything else is a file path
+    fn from(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+impl InputSource {
+    fn read(&self) -> Result<String> {
+        match self {
+            InputSource::File(path) => input::load(path),
+            InputSource::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                Ok(input::normalize(&content))
+            }
+        }
+    }
+}
+
+/// What `process_day` should run and on which input
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Only solve this part, both when not set
+    pub part: Option<DayPart>,
+    /// Use this input instead of the cached `data/{year}/{day}.txt`
+    pub input: Option<InputSource>,
+    /// Give up on a part that runs longer than this
+    pub timeout: Option<Duration>,
+}
+
+impl RunOptions {
+    fn runs(&self, part: DayPart) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
 fn day_part_to_str(part: DayPart) -> String {
     match part {
         DayPart::Part1 => String::from("part1"),
@@ -31,93 +205,241 @@ fn day_part_to_str(part: DayPart) -> String {
     }
 }
 
-fn parse<T: Day + FromStr>(file: &str) -> Result<T>
+fn alloc_suffix(alloc: &Option<AllocStats>) -> String {
+    alloc.map(|a| format!(" ({})", a)).unwrap_or_default()
+}
+
+fn parse<T>(content: String, timeout: Option<Duration>) -> Result<(T, Duration, Option<AllocStats>)>
 where
+    T: Day + FromStr + Send + 'static,
     <T as FromStr>::Err: Send + Sync + 'static,
     Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
 {
-    let time_tracker = Instant::now();
-    let content = fs::read_to_string(file)?;
-    let day = content
-        .parse::<T>()
-        .context(format!("can't parse '{}'", content))?;
-    debug!("Parsing took: {}ms", time_tracker.elapsed().as_millis());
-    Ok(day)
+    let (outcome, alloc) = measure(|| {
+        run_isolated(worker_name::<T>("parse"), timeout, move || {
+            let time_tracker = Instant::now();
+            (content.parse::<T>(), time_tracker.elapsed())
+        })
+    });
+    let (day, elapsed) = outcome?
+        .map_err(|failure| anyhow!("{}", failure))
+        .context("can't parse input")?;
+    let day = day.context("can't parse input")?;
+    debug!("Parsing took: {:.3?}{}", elapsed, alloc_suffix(&alloc));
+    Ok((day, elapsed, alloc))
 }
 
-fn solve<T: Day + FromStr>(day: T, part: DayPart) -> Result<()> {
-    let time_tracker = Instant::now();
-    match part {
-        DayPart::Part1 => {
-            let result = day.part1()?;
-            info!("Part 1 answer is: {}", result);
-        }
-        DayPart::Part2 => {
-            let result = day.part2()?;
-            info!("Part 2 answer is: {}", result);
+fn prepare<T>(
+    day: &Arc<T>,
+    timeout: Option<Duration>,
+) -> Result<(T::Prepared, Duration, Option<AllocStats>)>
+where
+    T: Day + FromStr + Send + Sync + 'static,
+    T::Prepared: Send + 'static,
+{
+    let day = Arc::clone(day);
+    let (outcome, alloc) = measure(|| {
+        run_isolated(worker_name::<T>("prepare"), timeout, move || {
+            let time_tracker = Instant::now();
+            (day.prepare(), time_tracker.elapsed())
+        })
+    });
+    let (prepared, elapsed) = outcome?
+        .map_err(|failure| anyhow!("{}", failure))
+        .context("can't prepare parsed input")?;
+    let prepared = prepared.context("can't prepare parsed input")?;
+    debug!("Preparing took: {:.3?}{}", elapsed, alloc_suffix(&alloc));
+    Ok((prepared, elapsed, alloc))
+}
+
+/// Thread name such as `2024/04 part2`, shown when a part panics
+fn worker_name<T: Day + FromStr>(what: &str) -> String {
+    let (year, day) = T::id();
+    format!("{}/{:02} {}", year, day, what)
+}
+
+fn solve<T>(
+    day: &Arc<T>,
+    prepared: &Arc<T::Prepared>,
+    part: DayPart,
+    timeout: Option<Duration>,
+) -> Result<PartResult>
+where
+    T: Day + FromStr + Send + Sync + 'static,
+    T::Prepared: Send + Sync + 'static,
+{
+    let (day, prepared) = (Arc::clone(day), Arc::clone(prepared));
+    let started = Instant::now();
+    // Measured from here so a part that times out doesn't keep the lock
+    let (outcome, alloc) = measure(|| {
+        run_isolated(
+            worker_name::<T>(&day_part_to_str(part)),
+            timeout,
+            move || {
+                let time_tracker = Instant::now();
+                let answer = match part {
+                    DayPart::Part1 => day.part1(&prepared).map(|a| a.to_string()),
+                    DayPart::Part2 => day.part2(&prepared).map(|a| a.to_string()),
+                };
+                (answer, time_tracker.elapsed())
+            },
+        )
+    });
+    let (answer, elapsed) = match outcome? {
+        Ok(solved) => solved,
+        Err(failure) => {
+            error!("Part {} failed: {}", part.level(), failure);
+            return Ok(PartResult::failed(failure, started.elapsed()));
         }
-    }
+    };
+    let answer = answer?;
+    info!("Part {} answer is: {}", part.level(), answer);
     debug!(
-        "Solving {} took: {}ms",
+        "Solving {} took: {:.3?}{}",
         day_part_to_str(part),
-        time_tracker.elapsed().as_millis()
+        elapsed,
+        alloc_suffix(&alloc)
+    );
+    Ok(PartResult {
+        answer,
+        elapsed,
+        alloc,
+        failure: None,
+    })
+}
+
+/// Combined solutions can't be measured per part, so the whole time and
+/// heap usage are reported against part 1.
+fn solve_both<T>(
+    day: &Arc<T>,
+    prepared: &Arc<T::Prepared>,
+    timeout: Option<Duration>,
+) -> Result<Option<(PartResult, PartResult)>>
+where
+    T: Day + FromStr + Send + Sync + 'static,
+    T::Prepared: Send + Sync + 'static,
+{
+    let (day, prepared) = (Arc::clone(day), Arc::clone(prepared));
+    let started = Instant::now();
+    let (outcome, alloc) = measure(|| {
+        run_isolated(worker_name::<T>("both parts"), timeout, move || {
+            let time_tracker = Instant::now();
+            let answers = day
+                .solve_both(&prepared)
+                .map(|answers| answers.map(|(a, b)| (a.to_string(), b.to_string())));
+            (answers, time_tracker.elapsed())
+        })
+    });
+    let (answers, elapsed) = match outcome? {
+        Ok(solved) => solved,
+        Err(failure) => {
+            error!("Solving both parts failed: {}", failure);
+            return Ok(Some((
+                PartResult::failed(failure.clone(), started.elapsed()),
+                PartResult::failed(failure, Duration::ZERO),
+            )));
+        }
+    };
+    let Some(answers) = answers else {
+        return Ok(None);
+    };
+    let (part1, part2) = answers?;
+    info!("Part 1 answer is: {}", part1);
+    info!("Part 2 answer is: {}", part2);
+    debug!(
+        "Solving both parts took: {:.3?}{}",
+        elapsed,
+        alloc_suffix(&alloc)
     );
-    Ok(())
+    Ok(Some((
+        PartResult {
+            answer: part1,
+            elapsed,
+            alloc,
+            failure: None,
+        },
+        PartResult {
+            answer: part2,
+            elapsed: Duration::ZERO,
+            alloc: alloc.map(|_| AllocStats::default()),
+            failure: None,
+        },
+    )))
 }
 
-pub fn process_day<T>(session_id: Option<String>) -> Result<()>
+/// Each part runs on a worker thread, so a panic or a timeout is recorded
+/// as that part's result instead of taking the whole run down. Parsing and
+/// preparing are isolated the same way, a failure there is the day's error.
+pub fn process_day<T>(client: &AocClient, options: &RunOptions) -> Result<DayResult>
 where
-    T: Day + FromStr + Clone,
+    T: Day + FromStr + Send + Sync + 'static,
+    T::Prepared: Send + Sync + 'static,
     <T as FromStr>::Err: Send + Sync + 'static,
     Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
 {
-    let (year, day) = <T>::id();
-    let data_file = fetch_input(session_id, year, day)?;
-    let day = parse::<T>(&data_file)?;
-    let _ = solve(day.clone(), DayPart::Part1)?;
-    let _ = solve(day, DayPart::Part2)?;
-    Ok(())
-}
-
-pub fn fetch_input(session_id: Option<String>, year: i32, day: i32) -> Result<String> {
-    let data_folder = format!("./data/{}", year);
-    let data_file = format!("{}/{}.txt", data_folder, day);
-    if fs::metadata(data_file.clone()).is_ok() {
-        return Ok(data_file);
-    }
-    debug!("Ifnput ile {} is missing... downloading...", &data_file);
-    let aoc_session_id = session_id.ok_or(anyhow!(
-        "Please set aoc_session_id parameter to allow download input file"
-    ))?;
-    // ensure data folder is present
-    fs::create_dir_all(data_folder)?;
-
-    // create an input file
-    let mut file = fs::File::create(data_file.clone())?;
-
-    let url = "https://adventofcode.com".parse::<Url>()?;
-    let cookie = format!("session={}", aoc_session_id);
-    let cookie_jar = Jar::default();
-    cookie_jar.add_cookie_str(&cookie, &url);
-    let client = ClientBuilder::new()
-        .cookie_provider(cookie_jar.into())
-        .build()?;
-    let mut resp = client
-        .get(format!(
-            "https://adventofcode.com/{}/day/{}/input",
-            year, day
-        ))
-        .send()?;
-    let _ = io::copy(&mut resp, &mut file)?;
-    Ok(data_file)
+    let id = <T>::id();
+    let (year, day) = id;
+    let content = match &options.input {
+        Some(input) => input.read()?,
+        None => input::load(fetch_input(client, year, day)?)?,
+    };
+    let (day, parse_time, parse_alloc) = parse::<T>(content, options.timeout)?;
+    let day = Arc::new(day);
+    let (prepared, prepare_time, prepare_alloc) = prepare(&day, options.timeout)?;
+    let prepared = Arc::new(prepared);
+    if options.part.is_none() {
+        if let Some((part1, part2)) = solve_both(&day, &prepared, options.timeout)? {
+            return Ok(DayResult {
+                id,
+                parse_time,
+                parse_alloc,
+                prepare_time,
+                prepare_alloc,
+                part1: Some(part1),
+                part2: Some(part2),
+            });
+        }
+    }
+    let part1 = match options.runs(DayPart::Part1) {
+        true => Some(solve(&day, &prepared, DayPart::Part1, options.timeout)?),
+        false => None,
+    };
+    let part2 = match options.runs(DayPart::Part2) {
+        true => Some(solve(&day, &prepared, DayPart::Part2, options.timeout)?),
+        false => None,
+    };
+    Ok(DayResult {
+        id,
+        parse_time,
+        parse_alloc,
+        prepare_time,
+        prepare_alloc,
+        part1,
+        part2,
+    })
+}
+
+/// Folder holding cached inputs and bookkeeping files for a year
+pub fn data_folder(year: i32) -> String {
+    format!("./data/{}", year)
+}
+
+/// Path such as `/tmp/aoc-1234-answers.toml` unique to the test run, with
+/// whatever a previous run left there removed.
+#[cfg(test)]
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&path);
+    let _ = std::fs::remove_file(&path);
+    path
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use anyhow::Result;
+    use std::fs;
 
-    #[derive(Clone)]
     struct TestDay {
         foo: i32,
     }
@@ -125,16 +447,23 @@ mod tests {
     impl Day for TestDay {
         type DayOutputPart1 = i32;
         type DayOutputPart2 = i32;
+        type Prepared = ();
+
+        const TITLE: &'static str = "Day 0: Test";
 
         fn id() -> AOCYearDay {
             (0, 0)
         }
 
-        fn part1(self) -> Result<Self::DayOutputPart1> {
+        fn prepare(&self) -> Result<Self::Prepared> {
+            Ok(())
+        }
+
+        fn part1(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart1> {
             Ok(self.foo)
         }
 
-        fn part2(self) -> Result<Self::DayOutputPart2> {
+        fn part2(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart2> {
             Ok(self.foo)
         }
     }
@@ -143,22 +472,194 @@ mod tests {
         type Err = anyhow::Error;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let foo = s.parse::<i32>()?;
+            let foo = parse_error::parse_at(s, s.trim())?;
             Ok(TestDay { foo })
         }
     }
 
+    /// `solve_both` answers part 2 differently so tests can tell which path ran
+    struct CombinedDay {
+        values: Vec<i32>,
+    }
+
+    impl Day for CombinedDay {
+        type DayOutputPart1 = i32;
+        type DayOutputPart2 = i32;
+        type Prepared = i32;
+
+        const TITLE: &'static str = "Day 0: Combined";
+
+        fn id() -> AOCYearDay {
+            (0, 0)
+        }
+
+        fn prepare(&self) -> Result<Self::Prepared> {
+            Ok(self.values.iter().copied().max().unwrap_or_default())
+        }
+
+        fn part1(&self, largest: &Self::Prepared) -> Result<Self::DayOutputPart1> {
+            Ok(*largest)
+        }
+
+        fn part2(&self, largest: &Self::Prepared) -> Result<Self::DayOutputPart2> {
+            Ok(largest * 2)
+        }
+
+        fn solve_both(
+            &self,
+            largest: &Self::Prepared,
+        ) -> Option<Result<(Self::DayOutputPart1, Self::DayOutputPart2)>> {
+            Some(Ok((*largest, largest * 2 + 1)))
+        }
+    }
+
+    impl FromStr for CombinedDay {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let values = s
+                .split_whitespace()
+                .map(|v| v.parse::<i32>())
+                .collect::<Result<_, _>>()?;
+            Ok(CombinedDay { values })
+        }
+    }
+
+    /// Part 1 panics, part 2 still has to be answered. Parsing `panic` and
+    /// preparing a zero panic too.
+    struct PanickyDay {
+        foo: i32,
+    }
+
+    impl Day for PanickyDay {
+        type DayOutputPart1 = i32;
+        type DayOutputPart2 = i32;
+        type Prepared = ();
+
+        const TITLE: &'static str = "Day 0: Panicky";
+
+        fn id() -> AOCYearDay {
+            (0, 0)
+        }
+
+        fn prepare(&self) -> Result<Self::Prepared> {
+            match self.foo {
+                0 => panic!("nothing to prepare"),
+                _ => Ok(()),
+            }
+        }
+
+        fn part1(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart1> {
+            panic!("no answer for {}", self.foo)
+        }
+
+        fn part2(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart2> {
+            Ok(self.foo)
+        }
+    }
+
+    impl FromStr for PanickyDay {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if s.trim() == "panic" {
+                panic!("can't read {}", s.trim());
+            }
+            Ok(PanickyDay {
+                foo: parse_error::parse_at(s, s.trim())?,
+            })
+        }
+    }
+
     #[test]
     fn test_parse_testday_bad_content() -> Result<()> {
-        let day = parse::<TestDay>("./data/0/bad.txt");
+        let day = parse::<TestDay>(fs::read_to_string("./data/0/bad.txt")?, None);
         assert!(day.is_err());
-        assert_eq!(day.err().unwrap().to_string(), "can't parse 'not a number'");
+        let error = day.err().unwrap();
+        assert_eq!(error.to_string(), "can't parse input");
+        assert_eq!(
+            error.root_cause().to_string(),
+            "line 1, column 1: 'not a number': invalid digit found in string\n\
+             1 | not a number\n  \
+               | ^"
+        );
         Ok(())
     }
 
     #[test]
     fn test_process_testday_good() -> Result<()> {
-        let _ = process_day::<TestDay>(None)?;
+        let result = process_day::<TestDay>(&AocClient::new(None), &RunOptions::default())?;
+        assert_eq!(result.answer(DayPart::Part1), Some("42"));
+        assert_eq!(result.answer(DayPart::Part2), Some("42"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_testday_single_part_custom_input() -> Result<()> {
+        let options = RunOptions {
+            part: Some(DayPart::Part2),
+            input: Some(InputSource::from("./data/0/custom.txt")),
+            timeout: None,
+        };
+        let result = process_day::<TestDay>(&AocClient::new(None), &options)?;
+        assert_eq!(result.answer(DayPart::Part1), None);
+        assert_eq!(result.answer(DayPart::Part2), Some("7"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_uses_solve_both_only_for_both_parts() -> Result<()> {
+        let client = AocClient::new(None);
+        let result = process_day::<CombinedDay>(&client, &RunOptions::default())?;
+        assert_eq!(result.answer(DayPart::Part1), Some("42"));
+        assert_eq!(result.answer(DayPart::Part2), Some("85"));
+
+        let options = RunOptions {
+            part: Some(DayPart::Part2),
+            input: None,
+            timeout: None,
+        };
+        let result = process_day::<CombinedDay>(&client, &options)?;
+        assert_eq!(result.answer(DayPart::Part2), Some("84"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_isolates_panicking_part() -> Result<()> {
+        let result = process_day::<PanickyDay>(&AocClient::new(None), &RunOptions::default())?;
+        assert_eq!(result.answer(DayPart::Part1), None);
+        assert_eq!(result.answer(DayPart::Part2), Some("42"));
+        assert_eq!(
+            result.failures().collect::<Vec<_>>(),
+            [&PartFailure::Panic(String::from("no answer for 42"))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_isolates_panicking_parse_and_prepare() -> Result<()> {
+        let error = parse::<PanickyDay>(String::from("panic\n"), None)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "can't parse input");
+        assert_eq!(error.root_cause().to_string(), "PANIC: can't read panic");
+
+        let error = prepare(&Arc::new(PanickyDay { foo: 0 }), None)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "can't prepare parsed input");
+        assert_eq!(error.root_cause().to_string(), "PANIC: nothing to prepare");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bench_testday() -> Result<()> {
+        let options = bench::BenchOptions {
+            iterations: 3,
+            warmup: 1,
+        };
+        let result = bench::bench_day::<TestDay>(&AocClient::new(None), &options)?;
+        assert!(result.part1.min <= result.part1.median);
         Ok(())
     }
 }
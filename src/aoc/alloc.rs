@@ -0,0 +1,169 @@
+#[cfg(feature = "alloc-stats")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+#[cfg(feature = "alloc-stats")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "alloc-stats")]
+use std::sync::{Mutex, PoisonError};
+
+#[cfg(feature = "alloc-stats")]
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "alloc-stats")]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "alloc-stats")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Held while a step is measured, the counters are shared by every thread
+#[cfg(feature = "alloc-stats")]
+static MEASURING: Mutex<()> = Mutex::new(());
+
+/// System allocator that keeps count of allocations and of the bytes in
+/// use, installed as the global allocator by the `alloc-stats` feature.
+#[cfg(feature = "alloc-stats")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-stats")]
+impl CountingAllocator {
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            CountingAllocator::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            CountingAllocator::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CountingAllocator::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CountingAllocator::shrink(layout.size());
+            CountingAllocator::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Heap used by one step, counted across every thread of the process
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Most bytes held at once on top of what was in use before the step
+    pub peak_bytes: usize,
+    pub allocations: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak in {} allocs",
+            format_bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Human friendly size such as `12.5 KiB`
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", size, UNITS[unit]),
+    }
+}
+
+/// Runs `step` and reports its heap usage, `None` unless the counting
+/// allocator is installed.
+///
+/// Only one step is measured at a time, other threads wait for their turn,
+/// so calls must not be nested. Work handed to a worker thread that may
+/// never finish should be measured from the thread waiting for it, so the
+/// lock is released when it gives up.
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<R>(step: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    (step(), None)
+}
+
+#[cfg(feature = "alloc-stats")]
+pub fn measure<R>(step: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    let _measuring = MEASURING.lock().unwrap_or_else(PoisonError::into_inner);
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let result = step();
+    let stats = AllocStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(before),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(12800), "12.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    fn check_measure() {
+        let (sum, stats) = measure(|| vec![1u8; 4096].iter().map(|b| *b as usize).sum::<usize>());
+        assert_eq!(sum, 4096);
+        if cfg!(feature = "alloc-stats") {
+            let stats = stats.unwrap();
+            assert!(stats.allocations >= 1);
+            assert!(stats.peak_bytes >= 4096);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        check_measure();
+    }
+
+    #[test]
+    fn test_measure_from_many_threads() {
+        let workers: Vec<_> = (0..8).map(|_| std::thread::spawn(check_measure)).collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{data_folder, toml_file, DayPart, DayResult};
+
+/// Accepted answers for a whole year, stored as `data/{year}/answers.toml`
+/// so every solved day can be re-checked after refactoring.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default, rename = "day")]
+    days: Vec<DayAnswers>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DayAnswers {
+    day: i32,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl DayAnswers {
+    fn answer(&self, part: DayPart) -> Option<&str> {
+        match part {
+            DayPart::Part1 => self.part1.as_deref(),
+            DayPart::Part2 => self.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "PASS"),
+            CheckStatus::Fail => write!(f, "FAIL"),
+            CheckStatus::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(year: i32) -> Result<Answers> {
+        Answers::load_from(Path::new(&data_folder(year)).join("answers.toml"))
+    }
+
+    fn load_from(path: PathBuf) -> Result<Answers> {
+        let mut answers: Answers = toml_file::load(&path, "answers")?;
+        answers.path = path;
+        Ok(answers)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        toml_file::save(&self.path, self, "answers")
+    }
+
+    pub fn get(&self, day: i32, part: DayPart) -> Option<&str> {
+        self.days
+            .iter()
+            .find(|d| d.day == day)
+            .and_then(|d| d.answer(part))
+    }
+
+    pub fn store(&mut self, day: i32, part: DayPart, answer: &str) {
+        let entry = toml_file::sorted_entry(
+            &mut self.days,
+            day,
+            |d| d.day,
+            || DayAnswers {
+                day,
+                ..Default::default()
+            },
+        );
+        match part {
+            DayPart::Part1 => entry.part1 = Some(answer.to_string()),
+            DayPart::Part2 => entry.part2 = Some(answer.to_string()),
+        }
+    }
+
+    /// Compares a freshly solved part with the accepted answer, a part that
+    /// panicked or timed out fails even without one.
+    pub fn check(&self, result: &DayResult, part: DayPart) -> CheckStatus {
+        if result.part(part).is_some_and(|p| p.failure.is_some()) {
+            return CheckStatus::Fail;
+        }
+        match self.get(result.id.1, part) {
+            None => CheckStatus::Missing,
+            Some(expected) if Some(expected) == result.answer(part) => CheckStatus::Pass,
+            Some(_) => CheckStatus::Fail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::aoc::isolate::PartFailure;
+    use crate::aoc::{temp_path, PartResult};
+    use anyhow::Result;
+
+    #[test]
+    fn test_store_and_check() -> Result<()> {
+        let path = temp_path("answers.toml");
+        let mut answers = Answers::load_from(path.clone())?;
+        answers.store(10, DayPart::Part1, "7");
+        answers.store(2, DayPart::Part2, "x");
+        answers.store(10, DayPart::Part2, "8");
+        answers.save()?;
+
+        let answers = Answers::load_from(path.clone())?;
+        assert_eq!(
+            answers.days.iter().map(|d| d.day).collect::<Vec<_>>(),
+            [2, 10]
+        );
+        let result = DayResult {
+            id: (2024, 10),
+            parse_time: Duration::ZERO,
+            parse_alloc: None,
+            prepare_time: Duration::ZERO,
+            prepare_alloc: None,
+            part1: Some(PartResult {
+                answer: String::from("7"),
+                elapsed: Duration::ZERO,
+                alloc: None,
+                failure: None,
+            }),
+            part2: Some(PartResult {
+                answer: String::from("9"),
+                elapsed: Duration::ZERO,
+                alloc: None,
+                failure: None,
+            }),
+        };
+        assert_eq!(answers.check(&result, DayPart::Part1), CheckStatus::Pass);
+        assert_eq!(answers.check(&result, DayPart::Part2), CheckStatus::Fail);
+        assert_eq!(answers.get(2, DayPart::Part1), None);
+
+        let timed_out = DayResult {
+            id: (2024, 2),
+            part1: Some(PartResult::failed(
+                PartFailure::Timeout(Duration::from_secs(1)),
+                Duration::from_secs(1),
+            )),
+            ..result
+        };
+        assert_eq!(answers.check(&timed_out, DayPart::Part1), CheckStatus::Fail);
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::bench::BenchResult;
+use super::{data_folder, toml_file};
+
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Parses a `--threshold` percentage, which has to be a finite number of at
+/// least 0 for comparisons to mean anything.
+pub fn parse_threshold(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(threshold) if threshold.is_finite() && threshold >= 0.0 => Ok(threshold),
+        Ok(_) => Err(String::from("expected a finite percentage of 0 or more")),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Benchmark medians for a whole year, stored as `data/{year}/baseline.toml`
+/// so later runs can tell when a change made a day slower.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default, rename = "day")]
+    days: Vec<DayBaseline>,
+}
+
+/// Median time of every step in nanoseconds
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DayBaseline {
+    day: i32,
+    parse_ns: u64,
+    prepare_ns: u64,
+    part1_ns: u64,
+    part2_ns: u64,
+}
+
+impl DayBaseline {
+    fn total(&self) -> Duration {
+        Duration::from_nanos(self.parse_ns + self.prepare_ns + self.part1_ns + self.part2_ns)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareStatus {
+    Ok,
+    Regressed,
+    New,
+}
+
+impl Display for CompareStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompareStatus::Ok => write!(f, "OK"),
+            CompareStatus::Regressed => write!(f, "REGRESSED"),
+            CompareStatus::New => write!(f, "NEW"),
+        }
+    }
+}
+
+/// Median time of a day in the baseline and in the current run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub baseline: Option<Duration>,
+    pub current: Duration,
+    pub status: CompareStatus,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive when the day got slower
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_nanos() as f64;
+        if baseline == 0.0 {
+            return None;
+        }
+        Some((self.current.as_nanos() as f64 - baseline) / baseline * 100.0)
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+impl Baseline {
+    pub fn load(year: i32) -> Result<Baseline> {
+        Baseline::load_from(Path::new(&data_folder(year)).join("baseline.toml"))
+    }
+
+    fn load_from(path: PathBuf) -> Result<Baseline> {
+        let mut baseline: Baseline = toml_file::load(&path, "baseline")?;
+        baseline.path = path;
+        Ok(baseline)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        toml_file::save(&self.path, self, "baseline")
+    }
+
+    /// Replaces the medians of the day with the ones just measured
+    pub fn store(&mut self, day: i32, result: &BenchResult) {
+        let entry = DayBaseline {
+            day,
+            parse_ns: nanos(result.parse.median),
+            prepare_ns: nanos(result.prepare.median),
+            part1_ns: nanos(result.part1.median),
+            part2_ns: nanos(result.part2.median),
+        };
+        *toml_file::sorted_entry(&mut self.days, day, |d| d.day, Default::default) = entry;
+    }
+
+    /// A day regressed when its total median grew by more than
+    /// `threshold` percent over the baseline.
+    pub fn compare(&self, day: i32, result: &BenchResult, threshold: f64) -> Comparison {
+        let current = result.median_total();
+        let baseline = self.days.iter().find(|d| d.day == day).map(|d| d.total());
+        let mut comparison = Comparison {
+            baseline,
+            current,
+            status: CompareStatus::New,
+        };
+        if let Some(change) = comparison.change() {
+            comparison.status = match change > threshold {
+                true => CompareStatus::Regressed,
+                false => CompareStatus::Ok,
+            };
+        }
+        comparison
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use crate::aoc::bench::Stats;
+    use crate::aoc::temp_path;
+    use anyhow::Result;
+
+    fn result(micros: u64) -> BenchResult {
+        let stats = Stats {
+            min: Duration::from_micros(micros),
+            median: Duration::from_micros(micros),
+            mean: Duration::from_micros(micros),
+            stddev: Duration::ZERO,
+        };
+        BenchResult {
+            parse: stats,
+            prepare: stats,
+            part1: stats,
+            part2: stats,
+        }
+    }
+
+    #[test]
+    fn test_store_and_compare() -> Result<()> {
+        let path = temp_path("baseline.toml");
+        let mut baseline = Baseline::load_from(path.clone())?;
+        baseline.store(4, &result(100));
+        baseline.store(2, &result(50));
+        baseline.save()?;
+
+        let baseline = Baseline::load_from(path.clone())?;
+        assert_eq!(
+            baseline.days.iter().map(|d| d.day).collect::<Vec<_>>(),
+            [2, 4]
+        );
+        let comparison = baseline.compare(4, &result(105), DEFAULT_THRESHOLD);
+        assert_eq!(comparison.baseline, Some(Duration::from_micros(400)));
+        assert_eq!(comparison.status, CompareStatus::Ok);
+        assert_eq!(comparison.change().map(|c| c.round()), Some(5.0));
+        assert_eq!(
+            baseline.compare(2, &result(60), DEFAULT_THRESHOLD).status,
+            CompareStatus::Regressed
+        );
+        assert_eq!(
+            baseline.compare(2, &result(60), 25.0).status,
+            CompareStatus::Ok
+        );
+        assert_eq!(
+            baseline.compare(1, &result(60), DEFAULT_THRESHOLD).status,
+            CompareStatus::New
+        );
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_threshold() {
+        assert_eq!(parse_threshold("12.5"), Ok(12.5));
+        assert_eq!(parse_threshold("0"), Ok(0.0));
+        assert!(parse_threshold("-1").is_err());
+        assert!(parse_threshold("NaN").is_err());
+        assert!(parse_threshold("inf").is_err());
+        assert!(parse_threshold("fast").is_err());
+    }
+}
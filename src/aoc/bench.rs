@@ -0,0 +1,161 @@
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use log::debug;
+
+use super::client::AocClient;
+use super::{fetch_input, input, Day};
+
+/// How many times each day is solved, warm-up runs are not measured
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    pub iterations: usize,
+    pub warmup: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            iterations: 100,
+            warmup: 5,
+        }
+    }
+}
+
+/// Summary of the measured runs of one step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Result<Stats> {
+        if samples.is_empty() {
+            return Err(anyhow!("can't compute statistics without samples"));
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+        let nanos: Vec<f64> = samples.iter().map(|s| s.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / nanos.len() as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / nanos.len() as f64;
+        Ok(Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+/// Timings of every step of a day over the real input
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub parse: Stats,
+    pub prepare: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl BenchResult {
+    /// Steps in the order they run, as shown in the bench table
+    pub fn steps(&self) -> [(&'static str, &Stats); 4] {
+        [
+            ("parse", &self.parse),
+            ("prepare", &self.prepare),
+            ("part 1", &self.part1),
+            ("part 2", &self.part2),
+        ]
+    }
+
+    /// Typical time of the whole day, used to compare with a baseline
+    pub fn median_total(&self) -> Duration {
+        self.steps().iter().map(|(_, stats)| stats.median).sum()
+    }
+}
+
+fn timed<R>(step: impl FnOnce() -> R) -> (R, Duration) {
+    let time_tracker = Instant::now();
+    let result = black_box(step());
+    (result, time_tracker.elapsed())
+}
+
+/// Parses and solves the cached input from scratch on every iteration so
+/// each step is measured on its own.
+fn bench_input<T>(content: &str, options: &BenchOptions) -> Result<[Vec<Duration>; 4]>
+where
+    T: Day + FromStr,
+    <T as FromStr>::Err: Send + Sync + 'static,
+    Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
+{
+    let mut samples: [Vec<Duration>; 4] = Default::default();
+    for iteration in 0..options.warmup + options.iterations {
+        let (day, parse) = timed(|| content.parse::<T>());
+        let day = day.context("can't parse input")?;
+        let (prepared, prepare) = timed(|| day.prepare());
+        let prepared = prepared?;
+        let (part1, part1_time) = timed(|| day.part1(&prepared));
+        part1?;
+        let (part2, part2_time) = timed(|| day.part2(&prepared));
+        part2?;
+        if iteration >= options.warmup {
+            for (step, elapsed) in [parse, prepare, part1_time, part2_time].iter().enumerate() {
+                samples[step].push(*elapsed);
+            }
+        }
+    }
+    Ok(samples)
+}
+
+pub fn bench_day<T>(client: &AocClient, options: &BenchOptions) -> Result<BenchResult>
+where
+    T: Day + FromStr,
+    <T as FromStr>::Err: Send + Sync + 'static,
+    Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
+{
+    let (year, day) = T::id();
+    let content = input::load(fetch_input(client, year, day)?)?;
+    debug!(
+        "Benchmarking day {} of {} with {} iterations after {} warm-up runs",
+        day, year, options.iterations, options.warmup
+    );
+    let [parse, prepare, part1, part2] = bench_input::<T>(&content, options)?;
+    Ok(BenchResult {
+        parse: Stats::from_samples(&parse)?,
+        prepare: Stats::from_samples(&prepare)?,
+        part1: Stats::from_samples(&part1)?,
+        part2: Stats::from_samples(&part2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_micros(*v)).collect()
+    }
+
+    #[test]
+    fn test_stats() -> Result<()> {
+        let stats = Stats::from_samples(&micros(&[4, 2, 9, 5]))?;
+        assert_eq!(stats.min, Duration::from_micros(2));
+        assert_eq!(stats.median, Duration::from_nanos(4500));
+        assert_eq!(stats.mean, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(2550));
+
+        let stats = Stats::from_samples(&micros(&[7, 1, 3]))?;
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert!(Stats::from_samples(&[]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use log::warn;
+
+use super::AOCYearDay;
+
+/// The whole event should be solved in under a second
+pub const DEFAULT_YEAR_BUDGET: Duration = Duration::from_secs(1);
+
+/// What happens when a day or a year takes longer than its budget
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BudgetMode {
+    Off,
+    #[default]
+    Warn,
+    Fail,
+}
+
+/// Time a solved day took, with the budget it declared if any
+#[derive(Debug, Clone, Copy)]
+pub struct Spent {
+    pub id: AOCYearDay,
+    pub budget: Option<Duration>,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overrun {
+    pub what: String,
+    pub time: Duration,
+    pub budget: Duration,
+}
+
+impl Display for Overrun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} took {:.3?}, over its budget of {:.3?}",
+            self.what, self.time, self.budget
+        )
+    }
+}
+
+/// Days over their own budget, then years whose days together took
+/// longer than `year_budget`.
+pub fn overruns(spent: &[Spent], year_budget: Duration) -> Vec<Overrun> {
+    let mut overruns: Vec<Overrun> = spent
+        .iter()
+        .filter_map(|s| {
+            let budget = s.budget?;
+            (s.time > budget).then(|| Overrun {
+                what: format!("{}/{:02}", s.id.0, s.id.1),
+                time: s.time,
+                budget,
+            })
+        })
+        .collect();
+    let mut years: BTreeMap<i32, Duration> = BTreeMap::new();
+    for s in spent {
+        *years.entry(s.id.0).or_default() += s.time;
+    }
+    overruns.extend(
+        years
+            .into_iter()
+            .filter(|(_, time)| *time > year_budget)
+            .map(|(year, time)| Overrun {
+                what: year.to_string(),
+                time,
+                budget: year_budget,
+            }),
+    );
+    overruns
+}
+
+/// Reports overruns as warnings, and as an error in `Fail` mode
+pub fn enforce(mode: BudgetMode, spent: &[Spent], year_budget: Duration) -> Result<()> {
+    if mode == BudgetMode::Off {
+        return Ok(());
+    }
+    let overruns = overruns(spent, year_budget);
+    for overrun in &overruns {
+        warn!("{}", overrun);
+    }
+    if mode == BudgetMode::Fail && !overruns.is_empty() {
+        return Err(anyhow!("{} time budget(s) exceeded", overruns.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spent(day: i32, budget: Option<u64>, time: u64) -> Spent {
+        Spent {
+            id: (2024, day),
+            budget: budget.map(Duration::from_millis),
+            time: Duration::from_millis(time),
+        }
+    }
+
+    #[test]
+    fn test_overruns() {
+        let days = [
+            spent(1, Some(10), 12),
+            spent(2, Some(500), 400),
+            spent(3, None, 700),
+        ];
+        assert_eq!(
+            overruns(&days, DEFAULT_YEAR_BUDGET),
+            [
+                Overrun {
+                    what: String::from("2024/01"),
+                    time: Duration::from_millis(12),
+                    budget: Duration::from_millis(10),
+                },
+                Overrun {
+                    what: String::from("2024"),
+                    time: Duration::from_millis(1112),
+                    budget: DEFAULT_YEAR_BUDGET,
+                },
+            ]
+        );
+        assert!(overruns(&days[1..2], DEFAULT_YEAR_BUDGET).is_empty());
+    }
+
+    #[test]
+    fn test_enforce() {
+        let days = [spent(1, Some(10), 12)];
+        assert!(enforce(BudgetMode::Warn, &days, DEFAULT_YEAR_BUDGET).is_ok());
+        assert!(enforce(BudgetMode::Off, &days, DEFAULT_YEAR_BUDGET).is_ok());
+        assert!(enforce(BudgetMode::Fail, &days, DEFAULT_YEAR_BUDGET).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use log::debug;
+use reqwest::{
+    blocking::{Client, ClientBuilder, RequestBuilder},
+    cookie::Jar,
+    Url,
+};
+use serde::{Deserialize, Serialize};
+
+use super::fetch::{check_response, validate_session, FetchError};
+use super::unlock::{format_remaining, Clock, SystemClock};
+
+pub const AOC_URL: &str = "https://adventofcode.com";
+
+/// AoC asks automated tools to identify themselves with a way to contact the author
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const STATE_FILE: &str = "./data/.last_request";
+
+/// Recorded next to every cached response as `{file}.stamp`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stamp {
+    pub url: String,
+    pub fetched_at: u64,
+    pub user_agent: String,
+}
+
+/// Everything needed to talk to adventofcode.com, shared by every feature
+/// that goes over the network so they all identify and throttle the same way.
+pub struct AocClient {
+    base_url: String,
+    session_id: Option<String>,
+    user_agent: String,
+    min_interval: Duration,
+    state_file: PathBuf,
+    clock: Box<dyn Clock>,
+    wait_for_unlock: bool,
+}
+
+impl AocClient {
+    pub fn new(session_id: Option<String>) -> Self {
+        AocClient {
+            base_url: String::from(AOC_URL),
+            session_id,
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            min_interval: DEFAULT_MIN_INTERVAL,
+            state_file: PathBuf::from(STATE_FILE),
+            clock: Box::new(SystemClock),
+            wait_for_unlock: false,
+        }
+    }
+
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Minimum time between two requests, shared by all runs of the tool
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Sleep until a locked puzzle becomes available instead of failing
+    pub fn wait_for_unlock(mut self, wait: bool) -> Self {
+        self.wait_for_unlock = wait;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_state_file(mut self, state_file: PathBuf) -> Self {
+        self.state_file = state_file;
+        self
+    }
+
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    pub fn waits_for_unlock(&self) -> bool {
+        self.wait_for_unlock
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Builds a blocking client that sends the AoC session cookie with every request
+    fn http(&self) -> Result<Client> {
+        let session_id = self
+            .session_id
+            .as_deref()
+            .ok_or(FetchError::MissingSession)?;
+        let session_id = validate_session(session_id)?;
+        let url = self.base_url.parse::<Url>()?;
+        let cookie = format!("session={}", session_id);
+        let cookie_jar = Jar::default();
+        cookie_jar.add_cookie_str(&cookie, &url);
+        let client = ClientBuilder::new()
+            .cookie_provider(cookie_jar.into())
+            .user_agent(&self.user_agent)
+            .build()?;
+        Ok(client)
+    }
+
+    fn now_millis(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request made by
+    /// any run, then records the new request time.
+    fn throttle(&self) -> Result<()> {
+        if self.min_interval.is_zero() {
+            return Ok(());
+        }
+        let last = fs::read_to_string(&self.state_file)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok());
+        if let Some(last) = last {
+            let next = last + self.min_interval.as_millis() as u64;
+            let now = self.now_millis();
+            if next > now {
+                let wait = Duration::from_millis(next - now);
+                debug!("Throttling request for {}", format_remaining(wait));
+                self.clock.sleep(wait);
+            }
+        }
+        if let Some(folder) = self.state_file.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(&self.state_file, self.now_millis().to_string())?;
+        Ok(())
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<String> {
+        self.throttle()?;
+        let resp = request.send()?;
+        let status = resp.status();
+        let body = resp.text()?;
+        check_response(status, &body)?;
+        Ok(body)
+    }
+
+    pub fn get(&self, path: &str) -> Result<String> {
+        debug!("GET {}", path);
+        self.send(self.http()?.get(self.url(path)))
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        debug!("POST {}", path);
+        self.send(self.http()?.post(self.url(path)).form(form))
+    }
+
+    /// Fetches a page and caches it in `file` together with a stamp saying
+    /// where and when it came from.
+    pub fn download(&self, path: &str, file: &Path) -> Result<()> {
+        let body = self.get(path)?;
+        write_atomically(file, &body)?;
+        let stamp = Stamp {
+            url: self.url(path),
+            fetched_at: self.now_millis() / 1000,
+            user_agent: self.user_agent.clone(),
+        };
+        write_atomically(&stamp_path(file), &toml::to_string(&stamp)?)
+    }
+}
+
+pub fn stamp_path(file: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.stamp", file.display()))
+}
+
+pub fn read_stamp(file: &Path) -> Result<Stamp> {
+    let path = stamp_path(file);
+    let content = fs::read_to_string(&path)?;
+    toml::from_str(&content).context(format!("can't parse stamp '{}'", path.display()))
+}
+
+/// Writes next to the target and renames, so an interrupted or failed
+/// download never leaves a partial file behind in the cache.
+fn write_atomically(path: &Path, content: &str) -> Result<()> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    let temp = PathBuf::from(format!("{}.download", path.display()));
+    if let Err(e) = fs::write(&temp, content).and_then(|_| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::temp_path;
+    use crate::aoc::test_server::{CannedResponse, TestServer};
+    use crate::aoc::unlock::tests::FakeClock;
+    use anyhow::Result;
+
+    #[test]
+    fn test_user_agent_is_sent() -> Result<()> {
+        let server = TestServer::start(vec![
+            CannedResponse::new(200, "first"),
+            CannedResponse::new(200, "second"),
+        ])?;
+        let _ = server.client().get("/2024")?;
+        let request = server.requests.recv()?;
+        assert_eq!(request.header("user-agent"), Some(DEFAULT_USER_AGENT));
+
+        let _ = server
+            .client()
+            .user_agent(String::from("me@example.com"))
+            .get("/2024")?;
+        let request = server.requests.recv()?;
+        assert_eq!(request.header("user-agent"), Some("me@example.com"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_throttle_persists_between_clients() -> Result<()> {
+        let server = TestServer::start(vec![
+            CannedResponse::new(200, "first"),
+            CannedResponse::new(200, "second"),
+        ])?;
+        let state_file = temp_path("client-last-request");
+        let start = 1_000_000;
+
+        let client = server
+            .client()
+            .min_interval(Duration::from_secs(10))
+            .with_state_file(state_file.clone())
+            .with_clock(FakeClock::at(start));
+        let _ = client.get("/2024")?;
+        assert_eq!(
+            client.clock().now(),
+            UNIX_EPOCH + Duration::from_secs(start)
+        );
+
+        // a separate run started 3 seconds later still has to wait for the rest
+        let client = server
+            .client()
+            .min_interval(Duration::from_secs(10))
+            .with_state_file(state_file.clone())
+            .with_clock(FakeClock::at(start + 3));
+        let _ = client.get("/2024")?;
+        assert_eq!(
+            client.clock().now(),
+            UNIX_EPOCH + Duration::from_secs(start + 10)
+        );
+        fs::remove_file(state_file)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_writes_stamp() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::new(200, "body")])?;
+        let file = temp_path("client-stamped").join("page.html");
+        let client = server.client().with_clock(FakeClock::at(1_700_000_000));
+        client.download("/2024/day/1", &file)?;
+        assert_eq!(fs::read_to_string(&file)?, "body");
+        let stamp = read_stamp(&file)?;
+        assert_eq!(stamp.url, format!("{}/2024/day/1", server.url));
+        assert_eq!(stamp.fetched_at, 1_700_000_000);
+        assert_eq!(stamp.user_agent, DEFAULT_USER_AGENT);
+        Ok(())
+    }
+}
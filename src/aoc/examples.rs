@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use log::info;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::client::AocClient;
+use super::fetch::wait_until_unlocked;
+use super::{data_folder, toml_file, DayPart};
+
+fn example_file(folder: &Path, day: i32, index: usize) -> PathBuf {
+    folder.join(format!("{}.example{}.txt", day, index))
+}
+
+/// Example input copied from the puzzle page, `data/{year}/{day}.example{N}.txt`
+#[cfg(test)]
+pub fn example_path(year: i32, day: i32, index: usize) -> PathBuf {
+    example_file(Path::new(&data_folder(year)), day, index)
+}
+
+/// Expected answers for the examples of a day, stored next to them as
+/// `data/{year}/{day}.examples.toml` so they can be fixed by hand when
+/// scraping picks the wrong number.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Examples {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default, rename = "example")]
+    examples: Vec<ExampleAnswers>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExampleAnswers {
+    index: usize,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Examples {
+    pub fn load(year: i32, day: i32) -> Result<Examples> {
+        Examples::load_from(Path::new(&data_folder(year)).join(format!("{}.examples.toml", day)))
+    }
+
+    fn load_from(path: PathBuf) -> Result<Examples> {
+        let mut examples: Examples = toml_file::load(&path, "examples")?;
+        examples.path = path;
+        Ok(examples)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        toml_file::save(&self.path, self, "example answers")
+    }
+
+    #[cfg(test)]
+    pub fn expected(&self, index: usize, part: DayPart) -> Option<&str> {
+        let example = self.examples.iter().find(|e| e.index == index)?;
+        match part {
+            DayPart::Part1 => example.part1.as_deref(),
+            DayPart::Part2 => example.part2.as_deref(),
+        }
+    }
+
+    /// Records a scraped answer unless one is already known, hand made
+    /// corrections survive fetching the examples again.
+    pub fn attach(&mut self, index: usize, part: DayPart, answer: &str) {
+        let example = toml_file::sorted_entry(
+            &mut self.examples,
+            index,
+            |e| e.index,
+            || ExampleAnswers {
+                index,
+                ..Default::default()
+            },
+        );
+        let known = match part {
+            DayPart::Part1 => &mut example.part1,
+            DayPart::Part2 => &mut example.part2,
+        };
+        known.get_or_insert_with(|| answer.to_string());
+    }
+}
+
+fn unescape(html: &str) -> String {
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(html, "");
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// What the puzzle page says about its examples
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    /// Distinct `<pre><code>` blocks in page order, part 2 often repeats
+    /// the example of part 1 and it is only kept once
+    pub inputs: Vec<String>,
+    /// Scraped answers as (example number, part, answer)
+    pub answers: Vec<(usize, DayPart, String)>,
+}
+
+/// Each part is described in its own `<article>`, its example answer is the
+/// last `<code><em>` of it and belongs to the latest example shown so far.
+pub fn extract_examples(html: &str) -> PuzzleExamples {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let block = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answer = Regex::new(r"<code><em>([^<]*)</em></code>").unwrap();
+
+    let mut examples = PuzzleExamples::default();
+    let mut latest = None;
+    for (position, article) in article.captures_iter(html).enumerate() {
+        let content = &article[1];
+        for code in block.captures_iter(content) {
+            let input = unescape(&code[1]);
+            let index = match examples.inputs.iter().position(|i| *i == input) {
+                Some(existing) => existing + 1,
+                None => {
+                    examples.inputs.push(input);
+                    examples.inputs.len()
+                }
+            };
+            latest = Some(index);
+        }
+        let part = match position {
+            0 => DayPart::Part1,
+            1 => DayPart::Part2,
+            _ => continue,
+        };
+        if let (Some(index), Some(found)) = (latest, answer.captures_iter(content).last()) {
+            examples
+                .answers
+                .push((index, part, unescape(&found[1]).trim().to_string()));
+        }
+    }
+    examples
+}
+
+/// Downloads the puzzle page and saves its examples and scraped answers,
+/// returns how many examples were found.
+pub fn fetch_examples(client: &AocClient, year: i32, day: i32) -> Result<usize> {
+    wait_until_unlocked(client, year, day)?;
+    let html = client.get(&format!("/{}/day/{}", year, day))?;
+    let folder = PathBuf::from(data_folder(year));
+    save_examples(
+        &extract_examples(&html),
+        &folder,
+        day,
+        Examples::load(year, day)?,
+    )
+}
+
+fn save_examples(
+    found: &PuzzleExamples,
+    folder: &Path,
+    day: i32,
+    mut examples: Examples,
+) -> Result<usize> {
+    fs::create_dir_all(folder)?;
+    for (index, input) in found.inputs.iter().enumerate() {
+        let path = example_file(folder, day, index + 1);
+        fs::write(&path, input)?;
+        info!("Saved example {}", path.display());
+    }
+    for (index, part, answer) in &found.answers {
+        examples.attach(*index, *part, answer);
+    }
+    examples.save()?;
+    Ok(found.inputs.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::temp_path;
+    use crate::aoc::test_server::{CannedResponse, TestServer};
+    use anyhow::Result;
+
+    const DAY1_EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn test_extract_examples() -> Result<()> {
+        let found = extract_examples(&fs::read_to_string("./data/0/responses/puzzle.html")?);
+        assert_eq!(
+            found.inputs,
+            [DAY1_EXAMPLE, "if a < b && c > d\n"].map(String::from)
+        );
+        assert_eq!(
+            found.answers,
+            [
+                (1, DayPart::Part1, String::from("11")),
+                (1, DayPart::Part2, String::from("31")),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_examples_keeps_known_answers() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::recorded(200, "puzzle.html")?])?;
+        let html = server.client().get("/2024/day/1")?;
+        assert_eq!(server.requests.recv()?.path, "/2024/day/1");
+
+        let folder = temp_path("examples");
+        let path = folder.join("1.examples.toml");
+        let mut examples = Examples::load_from(path.clone())?;
+        examples.attach(1, DayPart::Part2, "fixed by hand");
+        assert_eq!(
+            save_examples(&extract_examples(&html), &folder, 1, examples)?,
+            2
+        );
+
+        assert_eq!(
+            fs::read_to_string(example_file(&folder, 1, 1))?,
+            DAY1_EXAMPLE
+        );
+        let examples = Examples::load_from(path)?;
+        assert_eq!(examples.expected(1, DayPart::Part1), Some("11"));
+        assert_eq!(examples.expected(1, DayPart::Part2), Some("fixed by hand"));
+        assert_eq!(examples.expected(2, DayPart::Part1), None);
+        fs::remove_dir_all(folder)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use log::{debug, info, warn};
+use reqwest::StatusCode;
+
+use super::client::{read_stamp, AocClient};
+use super::data_folder;
+use super::unlock::{format_remaining, time_until_unlock};
+
+/// Ways talking to adventofcode.com can fail that are worth telling apart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// No session cookie was given at all
+    MissingSession,
+    /// Session cookie is malformed, expired or was rejected by AoC
+    InvalidSession(String),
+    /// Puzzle is not unlocked yet, with the time left when it is known
+    NotUnlocked(Option<Duration>),
+    NotFound,
+    ServerError(u16),
+    UnexpectedStatus(u16),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(
+                f,
+                "Please set aoc_session_id parameter to allow requests to adventofcode.com"
+            ),
+            FetchError::InvalidSession(reason) => write!(
+                f,
+                "AoC session is invalid or expired ({}), please refresh aoc_session_id",
+                reason
+            ),
+            FetchError::NotUnlocked(None) => write!(f, "Puzzle is not unlocked yet"),
+            FetchError::NotUnlocked(Some(remaining)) => write!(
+                f,
+                "Puzzle unlocks in {}, use --wait to fetch it as soon as it does",
+                format_remaining(*remaining)
+            ),
+            FetchError::NotFound => write!(f, "Page not found on adventofcode.com"),
+            FetchError::ServerError(status) => write!(
+                f,
+                "adventofcode.com answered with server error {} (it also does so for malformed session cookies)",
+                status
+            ),
+            FetchError::UnexpectedStatus(status) => {
+                write!(f, "adventofcode.com answered with unexpected status {}", status)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+const LOGIN_MARKERS: [&str; 2] = ["Please log in", "differ by user"];
+const NOT_UNLOCKED_MARKER: &str = "before it unlocks";
+
+/// Maps a response to a `FetchError` unless it carries real content
+pub fn check_response(status: StatusCode, body: &str) -> Result<(), FetchError> {
+    if LOGIN_MARKERS.iter().any(|m| body.contains(m)) {
+        return Err(FetchError::InvalidSession(String::from(
+            "adventofcode.com asked to log in",
+        )));
+    }
+    match status {
+        s if s.is_success() => Ok(()),
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(
+            FetchError::InvalidSession(format!("request rejected with {}", status.as_u16())),
+        ),
+        StatusCode::NOT_FOUND if body.contains(NOT_UNLOCKED_MARKER) => {
+            Err(FetchError::NotUnlocked(None))
+        }
+        StatusCode::NOT_FOUND => Err(FetchError::NotFound),
+        s if s.is_server_error() => Err(FetchError::ServerError(s.as_u16())),
+        s => Err(FetchError::UnexpectedStatus(s.as_u16())),
+    }
+}
+
+/// Cached files written by older versions could hold an AoC error page
+/// instead of the puzzle input.
+fn is_error_page(content: &str) -> bool {
+    content.contains(NOT_UNLOCKED_MARKER)
+        || LOGIN_MARKERS.iter().any(|m| content.contains(m))
+        || content.starts_with("<!DOCTYPE")
+        || content.starts_with("500 Internal Server Error")
+}
+
+/// Session cookies are hex strings, anything else is rejected before
+/// a request is made. A `session=` prefix copied from the browser is accepted.
+pub fn validate_session(session_id: &str) -> Result<String, FetchError> {
+    let session = session_id.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    if session.is_empty() {
+        return Err(FetchError::InvalidSession(String::from("it is empty")));
+    }
+    if !session.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(FetchError::InvalidSession(String::from(
+            "it should only contain hex digits",
+        )));
+    }
+    Ok(session.to_string())
+}
+
+/// Fails fast for a locked puzzle, or sleeps until it unlocks when the
+/// client was asked to wait.
+pub fn wait_until_unlocked(client: &AocClient, year: i32, day: i32) -> Result<()> {
+    if let Some(remaining) = time_until_unlock(client.clock(), year, day)? {
+        if !client.waits_for_unlock() {
+            return Err(FetchError::NotUnlocked(Some(remaining)).into());
+        }
+        info!(
+            "Day {} of {} unlocks in {}, waiting...",
+            day,
+            year,
+            format_remaining(remaining)
+        );
+        client.clock().sleep(remaining);
+    }
+    Ok(())
+}
+
+pub fn fetch_input(client: &AocClient, year: i32, day: i32) -> Result<String> {
+    let data_file = format!("{}/{}.txt", data_folder(year), day);
+    fetch_input_to(client, year, day, Path::new(&data_file))?;
+    Ok(data_file)
+}
+
+fn fetch_input_to(client: &AocClient, year: i32, day: i32, data_file: &Path) -> Result<()> {
+    if let Ok(content) = fs::read_to_string(data_file) {
+        if !is_error_page(&content) {
+            if let Ok(stamp) = read_stamp(data_file) {
+                debug!("Using {} cached at {}", stamp.url, stamp.fetched_at);
+            }
+            return Ok(());
+        }
+        warn!(
+            "Cached input {} holds an AoC error page, downloading it again",
+            data_file.display()
+        );
+        fs::remove_file(data_file)?;
+    }
+    debug!(
+        "Input file {} is missing... downloading...",
+        data_file.display()
+    );
+    wait_until_unlocked(client, year, day)?;
+    client.download(&format!("/{}/day/{}/input", year, day), data_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::UNIX_EPOCH;
+
+    use crate::aoc::temp_path;
+    use crate::aoc::test_server::{CannedResponse, TestServer};
+    use crate::aoc::unlock::tests::FakeClock;
+    use anyhow::Result;
+
+    const DAY1_UNLOCK: u64 = 1733029200;
+
+    fn temp_input(name: &str) -> PathBuf {
+        temp_path(&format!("fetch-{}", name)).join("1.txt")
+    }
+
+    fn fetch_error(response: CannedResponse, name: &str) -> Result<FetchError> {
+        let server = TestServer::start(vec![response])?;
+        let data_file = temp_input(name);
+        let err = fetch_input_to(&server.client(), 2024, 1, &data_file).unwrap_err();
+        assert!(!data_file.exists());
+        assert!(!PathBuf::from(format!("{}.download", data_file.display())).exists());
+        Ok(err.downcast_ref::<FetchError>().unwrap().clone())
+    }
+
+    #[test]
+    fn test_fetch_input_saves_body() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::new(200, "3   4\n4   3\n")])?;
+        let data_file = temp_input("ok");
+        fetch_input_to(&server.client(), 2024, 1, &data_file)?;
+        assert_eq!(fs::read_to_string(&data_file)?, "3   4\n4   3\n");
+        let request = server.requests.recv()?;
+        assert_eq!(request.path, "/2024/day/1/input");
+        assert_eq!(request.header("cookie"), Some("session=53616c7465645f5f"));
+        assert!(read_stamp(&data_file)?.url.ends_with("/2024/day/1/input"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_input_error_statuses() -> Result<()> {
+        assert!(matches!(
+            fetch_error(
+                CannedResponse::new(
+                    400,
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                ),
+                "login"
+            )?,
+            FetchError::InvalidSession(_)
+        ));
+        assert_eq!(
+            fetch_error(
+                CannedResponse::new(
+                    404,
+                    "Please don't repeatedly request this endpoint before it unlocks! \
+                     The calendar countdown is synchronized with the server time; \
+                     the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+                ),
+                "locked"
+            )?,
+            FetchError::NotUnlocked(None)
+        );
+        assert_eq!(
+            fetch_error(CannedResponse::new(404, "404 Not Found"), "missing")?,
+            FetchError::NotFound
+        );
+        assert_eq!(
+            fetch_error(
+                CannedResponse::new(500, "500 Internal Server Error"),
+                "server"
+            )?,
+            FetchError::ServerError(500)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_locked_day_fails_fast() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::new(200, "42\n")])?;
+        let data_file = temp_input("locked-fast");
+        let client = server
+            .client()
+            .with_clock(FakeClock::at(DAY1_UNLOCK - 3600));
+        let err = fetch_input_to(&client, 2024, 1, &data_file).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<FetchError>(),
+            Some(&FetchError::NotUnlocked(Some(Duration::from_secs(3600))))
+        );
+        assert!(server.requests.try_recv().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_locked_day_waits_for_unlock() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::new(200, "42\n")])?;
+        let data_file = temp_input("locked-wait");
+        let client = server
+            .client()
+            .with_clock(FakeClock::at(DAY1_UNLOCK - 3600))
+            .wait_for_unlock(true);
+        fetch_input_to(&client, 2024, 1, &data_file)?;
+        assert_eq!(fs::read_to_string(&data_file)?, "42\n");
+        assert_eq!(
+            client.clock().now(),
+            UNIX_EPOCH + Duration::from_secs(DAY1_UNLOCK)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_poisoned_cache_is_replaced() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::new(200, "42\n")])?;
+        let data_file = temp_input("poisoned");
+        fs::create_dir_all(data_file.parent().unwrap())?;
+        fs::write(
+            &data_file,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )?;
+        fetch_input_to(&server.client(), 2024, 1, &data_file)?;
+        assert_eq!(fs::read_to_string(&data_file)?, "42\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_session() {
+        assert_eq!(
+            validate_session(" session=abc123\n"),
+            Ok(String::from("abc123"))
+        );
+        assert!(matches!(
+            validate_session(""),
+            Err(FetchError::InvalidSession(_))
+        ));
+        assert!(matches!(
+            validate_session("not a cookie"),
+            Err(FetchError::InvalidSession(_))
+        ));
+    }
+}
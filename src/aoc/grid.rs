@@ -0,0 +1,334 @@
+// Not every helper has a day using it yet, the tests cover all of them
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use anyhow::Result;
+use ndarray::{s, Array2, ArrayView1, ArrayView2};
+
+use super::input;
+
+/// Row and column of a cell, rows go down and columns go right
+pub(crate) type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4-neighbourhood, clockwise from north
+    pub(crate) const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+    pub(crate) const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+    /// The 8-neighbourhood, clockwise from north
+    pub(crate) const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Change of row and column for a single step
+    pub(crate) fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// Direction taking a single step of `delta`, if there is one
+    pub(crate) fn from_delta(delta: (isize, isize)) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|d| d.delta() == delta)
+    }
+
+    pub(crate) fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::East => Direction::West,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::South => Direction::North,
+            Direction::SouthWest => Direction::NorthEast,
+            Direction::West => Direction::East,
+            Direction::NorthWest => Direction::SouthEast,
+        }
+    }
+}
+
+/// Rectangular puzzle map indexed by `(row, column)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Grid<T> {
+    cells: Array2<T>,
+}
+
+/// Diagonal of `view` where `column - row == k`, if it has any cell
+fn diagonal_of<T>(view: ArrayView2<'_, T>, k: isize) -> Option<ArrayView1<'_, T>> {
+    let (rows, columns) = view.dim();
+    if k >= columns as isize || -k >= rows as isize {
+        return None;
+    }
+    let view = match k >= 0 {
+        true => view.slice_move(s![.., k..]),
+        false => view.slice_move(s![-k.., ..]),
+    };
+    Some(view.into_diag())
+}
+
+impl<T> Grid<T> {
+    pub(crate) fn new(cells: Array2<T>) -> Grid<T> {
+        Grid { cells }
+    }
+
+    pub(crate) fn cells(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub(crate) fn rows(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub(crate) fn columns(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub(crate) fn contains(&self, (row, column): Pos) -> bool {
+        row < self.rows() && column < self.columns()
+    }
+
+    pub(crate) fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    /// Every position, row by row
+    pub(crate) fn positions(&self) -> impl Iterator<Item = Pos> {
+        let columns = self.columns();
+        (0..self.rows()).flat_map(move |row| (0..columns).map(move |column| (row, column)))
+    }
+
+    /// `pos` moved by `(rows, columns)`, `None` when that leaves the grid
+    pub(crate) fn offset(
+        &self,
+        (row, column): Pos,
+        (rows, columns): (isize, isize),
+    ) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(rows)?,
+            column.checked_add_signed(columns)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// `pos` moved by `(rows, columns)` on a torus, leaving the grid on one
+    /// side comes back in on the other. `None` for an empty grid.
+    pub(crate) fn wrapping_offset(
+        &self,
+        (row, column): Pos,
+        (rows, columns): (isize, isize),
+    ) -> Option<Pos> {
+        if self.rows() == 0 || self.columns() == 0 {
+            return None;
+        }
+        let wrap = |value: usize, delta: isize, size: usize| {
+            (value as isize + delta).rem_euclid(size as isize) as usize
+        };
+        Some((
+            wrap(row, rows, self.rows()),
+            wrap(column, columns, self.columns()),
+        ))
+    }
+
+    pub(crate) fn neighbour(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        self.offset(pos, direction.delta())
+    }
+
+    /// Neighbours of `pos` that are inside the grid, use
+    /// `Direction::ORTHOGONAL` or `Direction::ALL` for the usual
+    /// neighbourhoods.
+    pub(crate) fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        directions
+            .iter()
+            .filter_map(move |direction| self.neighbour(pos, *direction))
+    }
+
+    /// Cells from `pos` included up to the edge of the grid
+    pub(crate) fn cells_from(&self, pos: Pos, direction: Direction) -> impl Iterator<Item = &T> {
+        std::iter::successors(self.contains(pos).then_some(pos), move |pos| {
+            self.neighbour(*pos, direction)
+        })
+        .map(|pos| &self.cells[pos])
+    }
+
+    pub(crate) fn row(&self, row: usize) -> Option<ArrayView1<'_, T>> {
+        (row < self.rows()).then(|| self.cells.row(row))
+    }
+
+    pub(crate) fn column(&self, column: usize) -> Option<ArrayView1<'_, T>> {
+        (column < self.columns()).then(|| self.cells.column(column))
+    }
+
+    /// Cells going down and right where `column - row == k`, so `0` is the
+    /// diagonal starting in the top left corner.
+    pub(crate) fn diagonal(&self, k: isize) -> Option<ArrayView1<'_, T>> {
+        diagonal_of(self.cells.view(), k)
+    }
+
+    /// Cells going down and left where `row + column == k`, so `0` is the
+    /// top left corner alone.
+    pub(crate) fn anti_diagonal(&self, k: usize) -> Option<ArrayView1<'_, T>> {
+        let mirrored = self.cells.slice(s![.., ..;-1]);
+        diagonal_of(mirrored, self.columns() as isize - 1 - k as isize)
+    }
+
+    pub(crate) fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.cells.map(cell))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        &self.cells[pos]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        &mut self.cells[pos]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Grid::new(input::grid(s)?))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.cells.rows().into_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "abcd\nefgh\nijkl\n";
+
+    fn text(cells: Option<ArrayView1<'_, char>>) -> Option<String> {
+        cells.map(|cells| cells.iter().collect())
+    }
+
+    #[test]
+    fn test_parse_and_display() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        assert_eq!((grid.rows(), grid.columns()), (3, 4));
+        assert_eq!(grid[(1, 3)], 'h');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(format!("{}\n", grid), GRID);
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ABCD\nEFGH\nIJKL"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_offsets_and_neighbours() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        assert_eq!(grid.offset((0, 0), (2, 3)), Some((2, 3)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((2, 3), (0, 1)), None);
+        assert_eq!(grid.neighbour((1, 1), Direction::NorthWest), Some((0, 0)));
+        assert_eq!(grid.neighbours((0, 0), &Direction::ORTHOGONAL).count(), 2);
+        assert_eq!(grid.neighbours((0, 0), &Direction::ALL).count(), 3);
+        assert_eq!(grid.neighbours((1, 1), &Direction::ALL).count(), 8);
+        assert_eq!(grid.wrapping_offset((0, 0), (-1, -1)), Some((2, 3)));
+        assert_eq!(grid.wrapping_offset((2, 3), (4, 9)), Some((0, 0)));
+        let empty = "".parse::<Grid<char>>()?;
+        assert_eq!(empty.wrapping_offset((0, 0), (1, 1)), None);
+        for direction in Direction::ALL {
+            let (rows, columns) = direction.delta();
+            assert_eq!(direction.opposite().delta(), (-rows, -columns));
+            assert_eq!(Direction::from_delta(direction.delta()), Some(direction));
+        }
+        assert_eq!(Direction::from_delta((2, 0)), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cells_from() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        let ray = |pos, direction| grid.cells_from(pos, direction).collect::<String>();
+        assert_eq!(ray((0, 1), Direction::East), "bcd");
+        assert_eq!(ray((2, 3), Direction::NorthWest), "lgb");
+        assert_eq!(ray((0, 0), Direction::North), "a");
+        assert_eq!(ray((3, 0), Direction::North), "");
+
+        let narrow = "a\nb\n".parse::<Grid<char>>()?;
+        assert_eq!(narrow.cells_from((0, 0), Direction::SouthEast).count(), 1);
+        assert_eq!(narrow.neighbours((1, 0), &Direction::ALL).count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_views() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        assert_eq!(text(grid.row(1)), Some(String::from("efgh")));
+        assert_eq!(text(grid.column(2)), Some(String::from("cgk")));
+        assert_eq!(text(grid.column(4)), None);
+        assert_eq!(text(grid.diagonal(0)), Some(String::from("afk")));
+        assert_eq!(text(grid.diagonal(2)), Some(String::from("ch")));
+        assert_eq!(text(grid.diagonal(-2)), Some(String::from("i")));
+        assert_eq!(text(grid.diagonal(4)), None);
+        assert_eq!(text(grid.anti_diagonal(0)), Some(String::from("a")));
+        assert_eq!(text(grid.anti_diagonal(3)), Some(String::from("dgj")));
+        assert_eq!(text(grid.anti_diagonal(5)), Some(String::from("l")));
+        assert_eq!(text(grid.anti_diagonal(6)), None);
+        Ok(())
+    }
+}
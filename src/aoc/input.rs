@@ -0,0 +1,129 @@
+// No day has its input in paragraphs yet
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ndarray::Array2;
+
+use super::parse_error::ParseError;
+
+const TAB_WIDTH: usize = 4;
+
+/// Puzzle input the way every day expects it: `\n` line endings, no BOM,
+/// no whitespace at the end of a line, tabs expanded to spaces so error
+/// snippets line up, and a single newline after the last non-blank line.
+pub(crate) fn normalize(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut normalized = String::with_capacity(content.len());
+    for line in content.lines() {
+        let mut column = 0;
+        for c in line.trim_end().chars() {
+            match c {
+                '\t' => {
+                    let width = TAB_WIDTH - column % TAB_WIDTH;
+                    normalized.push_str(&" ".repeat(width));
+                    column += width;
+                }
+                c => {
+                    normalized.push(c);
+                    column += 1;
+                }
+            }
+        }
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Reads and normalizes an input file
+pub(crate) fn load(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let content =
+        fs::read_to_string(path).context(format!("can't read input '{}'", path.display()))?;
+    Ok(normalize(&content))
+}
+
+/// Non-blank lines of the input
+pub(crate) fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .lines()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.is_empty())
+}
+
+/// Blocks of lines separated by blank lines, without their line breaks
+/// around them.
+pub(crate) fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim_matches('\n'))
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+/// Characters of the input as rows and columns, every line must be as long
+/// as the first one.
+pub(crate) fn grid(input: &str) -> Result<Array2<char>> {
+    let rows: Vec<&str> = lines(input).collect();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    if let Some(row) = rows.iter().find(|row| row.chars().count() != width) {
+        return Err(ParseError::at_slice(
+            input,
+            row,
+            format!(
+                "expected {} cells like the first row, got {}",
+                width,
+                row.chars().count()
+            ),
+        )
+        .into());
+    }
+    let cells: Vec<char> = rows.iter().flat_map(|row| row.chars()).collect();
+    Ok(Array2::from_shape_vec((rows.len(), width), cells)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("\u{feff}3   4 \r\n4\t3\r\n\r\n\r\n"),
+            "3   4\n4   3\n"
+        );
+        assert_eq!(normalize("ab\tc\n\tx"), "ab  c\n    x\n");
+        assert_eq!(normalize("a\n\n  \nb"), "a\n\n\nb\n");
+        assert_eq!(normalize("\n \n"), "");
+    }
+
+    #[test]
+    fn test_lines_and_paragraphs() {
+        let input = "1 2\n\n3 4\n5 6\n\n\n7\n";
+        assert_eq!(lines(input).collect::<Vec<_>>(), ["1 2", "3 4", "5 6", "7"]);
+        assert_eq!(
+            paragraphs(input).collect::<Vec<_>>(),
+            ["1 2", "3 4\n5 6", "7"]
+        );
+        assert_eq!(lines("a\r\nb\r\n").collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_grid() -> Result<()> {
+        let grid = grid("abc\ndef\n")?;
+        assert_eq!(grid.shape(), [2, 3]);
+        assert_eq!(grid[[1, 0]], 'd');
+        assert_eq!(grid[[0, 2]], 'c');
+
+        let error = super::grid("abc\nde\nfgh\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected 3 cells like the first row, got 2");
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use std::any::Any;
+use std::fmt::Display;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+
+/// Why a part has no answer even though it was run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartFailure {
+    /// Still running when the timeout expired, the worker is left behind
+    Timeout(Duration),
+    Panic(String),
+}
+
+impl Display for PartFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartFailure::Timeout(timeout) => write!(f, "TIMEOUT after {:?}", timeout),
+            PartFailure::Panic(message) => write!(f, "PANIC: {}", message),
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("unknown panic payload"),
+        },
+    }
+}
+
+/// Runs `work` on its own thread so a panic or a hang stays contained.
+/// The outer error is for failing to start the thread at all.
+pub fn run_isolated<R, W>(
+    name: String,
+    timeout: Option<Duration>,
+    work: W,
+) -> Result<Result<R, PartFailure>>
+where
+    R: Send + 'static,
+    W: FnOnce() -> R + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::Builder::new().name(name).spawn(move || {
+        let _ = sender.send(work());
+    })?;
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    Ok(match received {
+        Ok(result) => {
+            let _ = worker.join();
+            Ok(result)
+        }
+        Err(RecvTimeoutError::Timeout) => Err(PartFailure::Timeout(timeout.unwrap_or_default())),
+        Err(RecvTimeoutError::Disconnected) => Err(PartFailure::Panic(match worker.join() {
+            Err(payload) => panic_message(payload),
+            Ok(_) => String::from("worker stopped without an answer"),
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_isolated() -> Result<()> {
+        assert_eq!(run_isolated(String::from("ok"), None, || 42)?, Ok(42));
+        assert_eq!(
+            run_isolated(String::from("panic"), None, || -> i32 {
+                panic!("index out of bounds")
+            })?,
+            Err(PartFailure::Panic(String::from("index out of bounds")))
+        );
+        let timeout = Duration::from_millis(20);
+        assert_eq!(
+            run_isolated(String::from("hang"), Some(timeout), || {
+                thread::sleep(Duration::from_secs(1))
+            })?,
+            Err(PartFailure::Timeout(timeout))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(
+            panic_message(Box::new(format!("at {}", 3))),
+            String::from("at 3")
+        );
+        assert_eq!(
+            panic_message(Box::new(7)),
+            String::from("unknown panic payload")
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::submit::Verdict;
+use super::{data_folder, toml_file, AOCYearDay, DayPart};
+
+/// Every answer submitted for a day together with what AoC said about it.
+///
+/// Stored as `data/{year}/{day}.ledger.toml` next to the cached input so it
+/// survives between runs and can be checked before burning a lockout.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    part1: PartLedger,
+    #[serde(default)]
+    part2: PartLedger,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PartLedger {
+    /// Answer AoC accepted for this part
+    accepted: Option<String>,
+    /// Largest answer known to be too low, any correct answer is above it
+    lower_bound: Option<i64>,
+    /// Smallest answer known to be too high, any correct answer is below it
+    upper_bound: Option<i64>,
+    #[serde(default)]
+    guesses: Vec<Guess>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Guess {
+    answer: String,
+    verdict: Verdict,
+}
+
+impl Ledger {
+    pub fn load((year, day): AOCYearDay) -> Result<Ledger> {
+        let path = Path::new(&data_folder(year)).join(format!("{}.ledger.toml", day));
+        Ledger::load_from(path)
+    }
+
+    fn load_from(path: PathBuf) -> Result<Ledger> {
+        let mut ledger: Ledger = toml_file::load(&path, "ledger")?;
+        ledger.path = path;
+        Ok(ledger)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        toml_file::save(&self.path, self, "ledger")
+    }
+
+    fn part(&self, part: DayPart) -> &PartLedger {
+        match part {
+            DayPart::Part1 => &self.part1,
+            DayPart::Part2 => &self.part2,
+        }
+    }
+
+    fn part_mut(&mut self, part: DayPart) -> &mut PartLedger {
+        match part {
+            DayPart::Part1 => &mut self.part1,
+            DayPart::Part2 => &mut self.part2,
+        }
+    }
+
+    /// Refuses answers that are already known to be wrong so they never reach AoC
+    pub fn check(&self, part: DayPart, answer: &str) -> Result<()> {
+        let ledger = self.part(part);
+        if let Some(accepted) = &ledger.accepted {
+            return Err(anyhow!(
+                "Part {} is already solved with '{}'",
+                part.level(),
+                accepted
+            ));
+        }
+        if let Some(guess) = ledger.guesses.iter().find(|g| g.answer == answer) {
+            return Err(anyhow!(
+                "'{}' was already submitted for part {}: {}",
+                answer,
+                part.level(),
+                guess.verdict
+            ));
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            if let Some(upper) = ledger.upper_bound.filter(|upper| value >= *upper) {
+                return Err(anyhow!(
+                    "'{}' can't be right for part {}, {} was already too high",
+                    answer,
+                    part.level(),
+                    upper
+                ));
+            }
+            if let Some(lower) = ledger.lower_bound.filter(|lower| value <= *lower) {
+                return Err(anyhow!(
+                    "'{}' can't be right for part {}, {} was already too low",
+                    answer,
+                    part.level(),
+                    lower
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remembers the verdict for a guess. Rate limited submissions were never
+    /// judged, so there is nothing to learn from them.
+    pub fn record(&mut self, part: DayPart, answer: &str, verdict: &Verdict) {
+        let ledger = self.part_mut(part);
+        let value = answer.parse::<i64>().ok();
+        match verdict {
+            Verdict::RateLimited(_) => return,
+            Verdict::Correct => ledger.accepted = Some(answer.to_string()),
+            Verdict::TooHigh => {
+                if let Some(value) = value {
+                    ledger.upper_bound = Some(ledger.upper_bound.map_or(value, |u| u.min(value)));
+                }
+            }
+            Verdict::TooLow => {
+                if let Some(value) = value {
+                    ledger.lower_bound = Some(ledger.lower_bound.map_or(value, |l| l.max(value)));
+                }
+            }
+            Verdict::Wrong | Verdict::AlreadySolved => {}
+        }
+        ledger.guesses.push(Guess {
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::aoc::temp_path;
+    use anyhow::Result;
+
+    fn temp_ledger(name: &str) -> PathBuf {
+        temp_path(&format!("{}.ledger.toml", name))
+    }
+
+    #[test]
+    fn test_refuses_known_wrong_and_out_of_bounds() -> Result<()> {
+        let mut ledger = Ledger::load_from(temp_ledger("bounds"))?;
+        ledger.record(DayPart::Part1, "100", &Verdict::TooHigh);
+        ledger.record(DayPart::Part1, "10", &Verdict::TooLow);
+        ledger.record(DayPart::Part1, "50", &Verdict::Wrong);
+
+        assert!(ledger.check(DayPart::Part1, "50").is_err());
+        assert!(ledger.check(DayPart::Part1, "100").is_err());
+        assert!(ledger.check(DayPart::Part1, "150").is_err());
+        assert!(ledger.check(DayPart::Part1, "10").is_err());
+        assert!(ledger.check(DayPart::Part1, "3").is_err());
+        assert!(ledger.check(DayPart::Part1, "42").is_ok());
+        assert!(ledger.check(DayPart::Part2, "150").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_refuses_after_accepted() -> Result<()> {
+        let mut ledger = Ledger::load_from(temp_ledger("accepted"))?;
+        ledger.record(DayPart::Part2, "abc", &Verdict::Correct);
+        assert!(ledger.check(DayPart::Part2, "abc").is_err());
+        assert!(ledger.check(DayPart::Part2, "def").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_limited_is_not_recorded() -> Result<()> {
+        let mut ledger = Ledger::load_from(temp_ledger("rate-limited"))?;
+        ledger.record(
+            DayPart::Part1,
+            "42",
+            &Verdict::RateLimited(Duration::from_secs(30)),
+        );
+        assert!(ledger.check(DayPart::Part1, "42").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ledger_roundtrip() -> Result<()> {
+        let path = temp_ledger("roundtrip");
+        let mut ledger = Ledger::load_from(path.clone())?;
+        ledger.record(DayPart::Part1, "100", &Verdict::TooHigh);
+        ledger.record(DayPart::Part1, "7", &Verdict::Correct);
+        ledger.save()?;
+
+        let ledger = Ledger::load_from(path.clone())?;
+        assert_eq!(ledger.part1.upper_bound, Some(100));
+        assert_eq!(ledger.part1.accepted, Some(String::from("7")));
+        assert_eq!(ledger.part1.guesses.len(), 2);
+        assert!(ledger.part2.guesses.is_empty());
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
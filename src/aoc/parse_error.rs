@@ -0,0 +1,131 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// How many characters of the offending line are shown on each side of the
+/// caret, puzzle inputs can have lines thousands of characters long.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Input that can't be parsed, pointing at the place where it went wrong
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Both line and column start at 1, the column counts characters
+    pub line: usize,
+    pub column: usize,
+    /// The whole offending line, trimmed down only when displayed
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Error at byte `offset` of `input`
+    pub fn at(input: &str, offset: usize, message: impl Display) -> ParseError {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Error at `part`, which has to be a slice of `input` such as a token
+    /// coming from `split_whitespace` or a regex match.
+    pub fn at_slice(input: &str, part: &str, message: impl Display) -> ParseError {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len());
+        debug_assert!(offset.is_some(), "'{}' is not a part of the input", part);
+        ParseError::at(input, offset.unwrap_or_default(), message)
+    }
+}
+
+/// Parses `token`, a slice of `input`, reporting where it is on failure
+pub fn parse_at<F>(input: &str, token: &str) -> Result<F, ParseError>
+where
+    F: FromStr,
+    <F as FromStr>::Err: Display,
+{
+    token
+        .parse::<F>()
+        .map_err(|e| ParseError::at_slice(input, token, format!("'{}': {}", token, e)))
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let skip = self.column.saturating_sub(SNIPPET_CONTEXT + 1);
+        let shown: String = self
+            .snippet
+            .chars()
+            .skip(skip)
+            .take(SNIPPET_CONTEXT * 2)
+            .collect();
+        let prefix = if skip > 0 { "..." } else { "" };
+        let suffix = match self.snippet.chars().count() > skip + SNIPPET_CONTEXT * 2 {
+            true => "...",
+            false => "",
+        };
+        let gutter = self.line.to_string();
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{} | {}{}{}", gutter, prefix, shown, suffix)?;
+        write!(
+            f,
+            "{} | {}^",
+            " ".repeat(gutter.len()),
+            " ".repeat(prefix.len() + self.column - 1 - skip)
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_slice() {
+        let input = "3   4\n4   x\n";
+        let token = input.split_whitespace().nth(3).unwrap();
+        let error = ParseError::at_slice(input, token, "not a number");
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.snippet, "4   x");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: not a number\n2 | 4   x\n  |     ^"
+        );
+        assert_eq!(ParseError::at(input, input.len(), "empty").line, 3);
+    }
+
+    #[test]
+    fn test_parse_at() {
+        let input = "1 2 3x";
+        let token = input.split_whitespace().last().unwrap();
+        assert_eq!(parse_at::<i32>(input, &input[..1]), Ok(1));
+        let error = parse_at::<i32>(input, token).unwrap_err();
+        assert_eq!(error.column, 5);
+        assert_eq!(error.message, "'3x': invalid digit found in string");
+    }
+
+    #[test]
+    fn test_long_line_is_shortened() {
+        let input = format!("{}?{}", "a".repeat(100), "b".repeat(100));
+        let error = ParseError::at(&input, 100, "unexpected '?'");
+        let shown = error.to_string();
+        let lines: Vec<&str> = shown.lines().collect();
+        assert_eq!(
+            lines[1],
+            format!("1 | ...{}?{}...", "a".repeat(40), "b".repeat(39))
+        );
+        assert_eq!(lines[2].find('^'), lines[1].find('?'));
+    }
+}
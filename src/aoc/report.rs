@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{AOCYearDay, DayPart, DayResult};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+/// Machine readable outcome of solving one part of a day
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub year: i32,
+    pub day: i32,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub parse_time_us: Option<u128>,
+    pub prepare_time_us: Option<u128>,
+    pub solve_time_us: Option<u128>,
+    pub peak_bytes: Option<usize>,
+    pub allocations: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// One record per solved part, failures of the whole day are reported
+    /// against both parts
+    pub fn from_result((year, day): AOCYearDay, result: &Result<DayResult>) -> Vec<Record> {
+        [DayPart::Part1, DayPart::Part2]
+            .into_iter()
+            .filter_map(|part| match result {
+                Ok(result) => result.part(part).map(|solved| Record {
+                    year,
+                    day,
+                    part: part.level(),
+                    answer: result.answer(part).map(String::from),
+                    parse_time_us: Some(result.parse_time.as_micros()),
+                    prepare_time_us: Some(result.prepare_time.as_micros()),
+                    solve_time_us: Some(solved.elapsed.as_micros()),
+                    peak_bytes: solved.alloc.map(|a| a.peak_bytes),
+                    allocations: solved.alloc.map(|a| a.allocations),
+                    error: solved.failure.as_ref().map(|f| f.to_string()),
+                }),
+                Err(e) => Some(Record {
+                    year,
+                    day,
+                    part: part.level(),
+                    answer: None,
+                    parse_time_us: None,
+                    prepare_time_us: None,
+                    solve_time_us: None,
+                    peak_bytes: None,
+                    allocations: None,
+                    error: Some(format!("{:#}", e)),
+                }),
+            })
+            .collect()
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
+
+pub fn to_json(records: &[Record]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(records)?)
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv =
+        String::from("year,day,part,answer,parse_time_us,prepare_time_us,solve_time_us,peak_bytes,allocations,error\n");
+    for record in records {
+        let row = [
+            record.year.to_string(),
+            record.day.to_string(),
+            record.part.to_string(),
+            csv_field(&optional(&record.answer)),
+            optional(&record.parse_time_us),
+            optional(&record.prepare_time_us),
+            optional(&record.solve_time_us),
+            optional(&record.peak_bytes),
+            optional(&record.allocations),
+            csv_field(&optional(&record.error)),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Plain text table with columns padded to their widest cell
+#[derive(Debug, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Table {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                if i < widths.len() {
+                    widths[i] = widths[i].max(cell.chars().count());
+                }
+            }
+        }
+
+        let write_row = |f: &mut std::fmt::Formatter<'_>, row: &[String]| {
+            let line = widths
+                .iter()
+                .enumerate()
+                .map(|(i, width)| {
+                    let cell = row.get(i).map(|c| c.as_str()).unwrap_or("");
+                    format!("{:<width$}", cell, width = width)
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+            writeln!(f, "{}", line.trim_end())
+        };
+
+        write_row(f, &self.headers)?;
+        let separator = widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("-+-");
+        writeln!(f, "{}", separator)?;
+        for row in &self.rows {
+            write_row(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use anyhow::anyhow;
+
+    use crate::aoc::alloc::AllocStats;
+    use crate::aoc::PartResult;
+
+    fn records() -> Vec<Record> {
+        let solved = Ok(DayResult {
+            id: (2024, 1),
+            parse_time: Duration::from_micros(15),
+            parse_alloc: None,
+            prepare_time: Duration::from_micros(7),
+            prepare_alloc: None,
+            part1: Some(PartResult {
+                answer: String::from("11"),
+                elapsed: Duration::from_micros(3),
+                alloc: Some(AllocStats {
+                    peak_bytes: 2048,
+                    allocations: 3,
+                }),
+                failure: None,
+            }),
+            part2: Some(PartResult {
+                answer: String::from("a,\"b\""),
+                elapsed: Duration::from_micros(1500),
+                alloc: None,
+                failure: None,
+            }),
+        });
+        let mut records = Record::from_result((2024, 1), &solved);
+        records.extend(Record::from_result((2024, 2), &Err(anyhow!("no input"))));
+        records
+    }
+
+    #[test]
+    fn test_csv_output() {
+        assert_eq!(
+            to_csv(&records()),
+            "\
+year,day,part,answer,parse_time_us,prepare_time_us,solve_time_us,peak_bytes,allocations,error
+2024,1,1,11,15,7,3,2048,3,
+2024,1,2,\"a,\"\"b\"\"\",15,7,1500,,,
+2024,2,1,,,,,,,no input
+2024,2,2,,,,,,,no input
+"
+        );
+    }
+
+    #[test]
+    fn test_json_output() -> Result<()> {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&records())?)?;
+        assert_eq!(json[0]["answer"], "11");
+        assert_eq!(json[1]["prepare_time_us"], 7);
+        assert_eq!(json[0]["allocations"], 3);
+        assert_eq!(json[1]["solve_time_us"], 1500);
+        assert_eq!(json[2]["answer"], serde_json::Value::Null);
+        assert_eq!(json[3]["error"], "no input");
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_pads_columns() {
+        let mut table = Table::new(&["Day", "Answer"]);
+        table.add_row(vec![String::from("Day10"), String::from("1")]);
+        table.add_row(vec![String::from("Day2"), String::from("12345")]);
+        assert_eq!(
+            table.to_string(),
+            "\
+Day   | Answer
+------+-------
+Day10 | 1
+Day2  | 12345
+"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use log::debug;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::client::AocClient;
+use super::{AOCYearDay, DayPart};
+
+/// Outcome of an answer submission as reported by the AoC answer page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    AlreadySolved,
+    RateLimited(Duration),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, answer is too high"),
+            Verdict::TooLow => write!(f, "wrong, answer is too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::RateLimited(wait) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+        }
+    }
+}
+
+/// Turns the answer page into a verdict, looking only at the `<article>`
+/// that carries the response message.
+pub fn parse_verdict(html: &str) -> Result<Verdict> {
+    let article_re = Regex::new(r"(?s)<article>(.*?)</article>")?;
+    let article = article_re
+        .captures(html)
+        .map(|c| c.get(1).unwrap().as_str())
+        .ok_or(anyhow!("Answer page has no <article> with a verdict"))?;
+
+    if article.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if article.contains("your answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if article.contains("your answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if article.contains("That's not the right answer") {
+        Ok(Verdict::Wrong)
+    } else if article.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::AlreadySolved)
+    } else if article.contains("You gave an answer too recently") {
+        let wait_re = Regex::new(r"You have (?:(?<m>\d+)m )?(?<s>\d+)s left to wait")?;
+        let wait = wait_re
+            .captures(article)
+            .map(|c| {
+                let minutes = c.name("m").map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds: u64 = c["s"].parse().unwrap_or(0);
+                Duration::from_secs(minutes * 60 + seconds)
+            })
+            .unwrap_or(Duration::from_secs(60));
+        Ok(Verdict::RateLimited(wait))
+    } else {
+        Err(anyhow!("Unrecognised answer page: {}", article.trim()))
+    }
+}
+
+pub fn submit_answer(
+    client: &AocClient,
+    (year, day): AOCYearDay,
+    part: DayPart,
+    answer: &str,
+) -> Result<Verdict> {
+    debug!(
+        "Submitting {} for {}/{} level {}",
+        answer,
+        year,
+        day,
+        part.level()
+    );
+    let level = part.level().to_string();
+    let body = client.post_form(
+        &format!("/{}/day/{}/answer", year, day),
+        &[("level", level.as_str()), ("answer", answer)],
+    )?;
+    parse_verdict(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::test_server::{CannedResponse, TestServer};
+    use anyhow::Result;
+
+    fn submit_recorded(page: &str) -> Result<Verdict> {
+        let server = TestServer::start(vec![CannedResponse::recorded(200, page)?])?;
+        submit_answer(&server.client(), (2024, 1), DayPart::Part1, "42")
+    }
+
+    #[test]
+    fn test_verdicts_from_recorded_pages() -> Result<()> {
+        assert_eq!(submit_recorded("correct.html")?, Verdict::Correct);
+        assert_eq!(submit_recorded("too_high.html")?, Verdict::TooHigh);
+        assert_eq!(submit_recorded("too_low.html")?, Verdict::TooLow);
+        assert_eq!(submit_recorded("wrong.html")?, Verdict::Wrong);
+        assert_eq!(
+            submit_recorded("already_solved.html")?,
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            submit_recorded("rate_limited.html")?,
+            Verdict::RateLimited(Duration::from_secs(34))
+        );
+        assert_eq!(
+            submit_recorded("rate_limited_minutes.html")?,
+            Verdict::RateLimited(Duration::from_secs(252))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_submission_request() -> Result<()> {
+        let server = TestServer::start(vec![CannedResponse::recorded(200, "correct.html")?])?;
+        let _ = submit_answer(&server.client(), (2024, 3), DayPart::Part2, "161")?;
+        let request = server.requests.recv()?;
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/2024/day/3/answer");
+        assert_eq!(request.header("cookie"), Some("session=53616c7465645f5f"));
+        assert_eq!(request.body, "level=2&answer=161");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unrecognised_page() {
+        let verdict = parse_verdict("<html><body>Nothing here</body></html>");
+        assert!(verdict.is_err());
+    }
+}
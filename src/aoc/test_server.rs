@@ -0,0 +1,124 @@
+//! Minimal HTTP server standing in for adventofcode.com in tests.
+//!
+//! Serves a fixed list of canned responses, one per connection, and records
+//! every request it receives so tests can assert on what was sent.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::client::AocClient;
+
+pub const SESSION: &str = "53616c7465645f5f";
+
+#[derive(Debug, Clone)]
+pub struct CannedResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl CannedResponse {
+    pub fn new(status: u16, body: &str) -> Self {
+        CannedResponse {
+            status,
+            body: body.to_string(),
+        }
+    }
+
+    /// Serves a recorded page from `data/0/responses`
+    pub fn recorded(status: u16, page: &str) -> Result<Self> {
+        let body = fs::read_to_string(format!("./data/0/responses/{}", page))?;
+        Ok(CannedResponse { status, body })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub struct TestServer {
+    pub url: String,
+    pub requests: Receiver<RecordedRequest>,
+}
+
+impl TestServer {
+    pub fn start(responses: Vec<CannedResponse>) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((k, v)) = line.split_once(':') {
+                        headers.push((k.trim().to_string(), v.trim().to_string()));
+                    }
+                }
+                let length = headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+                    .and_then(|(_, v)| v.parse::<usize>().ok())
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let _ = sender.send(RecordedRequest {
+                    method,
+                    path,
+                    headers,
+                    body: String::from_utf8_lossy(&body).to_string(),
+                });
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} Canned\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.status,
+                    response.body.len(),
+                    response.body
+                );
+            }
+        });
+        Ok(TestServer { url, requests })
+    }
+
+    /// Client pointed at this server with throttling disabled
+    pub fn client(&self) -> AocClient {
+        AocClient::new(Some(SESSION.into()))
+            .with_base_url(&self.url)
+            .min_interval(Duration::ZERO)
+    }
+}
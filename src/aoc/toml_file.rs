@@ -0,0 +1,90 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use log::debug;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Reads a TOML document kept under `data/`, a file that doesn't exist yet
+/// gives the default value. Other read errors are returned rather than
+/// letting the next `save` overwrite the file. `what` names it in errors,
+/// e.g. `ledger`.
+pub fn load<T: DeserializeOwned + Default>(path: &Path, what: &str) -> Result<T> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(T::default()),
+        Err(error) => {
+            return Err(error).context(format!("can't read {} '{}'", what, path.display()))
+        }
+    };
+    toml::from_str::<T>(&content).context(format!("can't parse {} '{}'", what, path.display()))
+}
+
+/// Writes a TOML document, creating its folder when needed
+pub fn save<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<()> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    fs::write(path, toml::to_string(value)?)?;
+    debug!("Saved {} to {}", what, path.display());
+    Ok(())
+}
+
+/// Entry of `items` with the given key, inserted with `new` where it keeps
+/// the list ordered by key when it is not there yet.
+pub fn sorted_entry<T, K: Ord>(
+    items: &mut Vec<T>,
+    key: K,
+    key_of: impl Fn(&T) -> K,
+    new: impl FnOnce() -> T,
+) -> &mut T {
+    let index = match items.iter().position(|item| key_of(item) == key) {
+        Some(index) => index,
+        None => {
+            let index = items.partition_point(|item| key_of(item) < key);
+            items.insert(index, new());
+            index
+        }
+    };
+    &mut items[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use crate::aoc::temp_path;
+
+    #[test]
+    fn test_load_and_save() -> Result<()> {
+        let path = temp_path("values.toml");
+        let mut values: BTreeMap<String, i32> = load(&path, "values")?;
+        assert!(values.is_empty());
+        values.insert(String::from("a"), 1);
+        save(&path, &values, "values")?;
+        assert_eq!(load::<BTreeMap<String, i32>>(&path, "values")?, values);
+
+        fs::write(&path, "a = ")?;
+        let error = load::<BTreeMap<String, i32>>(&path, "values").unwrap_err();
+        assert!(error.to_string().starts_with("can't parse values"));
+        fs::remove_file(&path)?;
+
+        fs::create_dir(&path)?;
+        let error = load::<BTreeMap<String, i32>>(&path, "values").unwrap_err();
+        assert!(error.to_string().starts_with("can't read values"));
+        fs::remove_dir(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorted_entry() {
+        let mut items = vec![(1, 'a'), (4, 'd')];
+        sorted_entry(&mut items, 3, |i| i.0, || (3, 'c'));
+        sorted_entry(&mut items, 4, |i| i.0, || (4, 'x')).1 = 'D';
+        sorted_entry(&mut items, 0, |i| i.0, || (0, '_'));
+        assert_eq!(items, [(0, '_'), (1, 'a'), (3, 'c'), (4, 'D')]);
+    }
+}
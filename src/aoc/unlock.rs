@@ -0,0 +1,139 @@
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+
+/// Source of the current time, swapped for a fake one in tests so the
+/// unlock schedule can be checked without real time passing.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) from December 1 to 25
+pub fn unlock_time(year: i32, day: i32) -> Result<SystemTime> {
+    if year < 2015 || !(1..=25).contains(&day) {
+        return Err(anyhow!(
+            "There is no Advent of Code puzzle for day {} of {}",
+            day,
+            year
+        ));
+    }
+    let days = days_from_civil(year as i64, 12, day as i64);
+    let seconds = days * 24 * 60 * 60 + 5 * 60 * 60;
+    Ok(UNIX_EPOCH + Duration::from_secs(seconds as u64))
+}
+
+/// How long until the puzzle unlocks, `None` once it is available
+pub fn time_until_unlock(clock: &dyn Clock, year: i32, day: i32) -> Result<Option<Duration>> {
+    let unlock = unlock_time(year, day)?;
+    Ok(unlock
+        .duration_since(clock.now())
+        .ok()
+        .filter(|d| !d.is_zero()))
+}
+
+/// Human friendly remaining time such as `2h 5m 3s`
+pub fn format_remaining(duration: Duration) -> String {
+    let total = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) = (
+        total / 86400,
+        total % 86400 / 3600,
+        total % 3600 / 60,
+        total % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", seconds),
+        (0, 0, _) => format!("{}m {}s", minutes, seconds),
+        (0, _, _) => format!("{}h {}m {}s", hours, minutes, seconds),
+        _ => format!("{}d {}h {}m {}s", days, hours, minutes, seconds),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Clock that only moves when something sleeps on it
+    pub struct FakeClock {
+        now: Mutex<SystemTime>,
+    }
+
+    impl FakeClock {
+        pub fn at(seconds: u64) -> Self {
+            FakeClock {
+                now: Mutex::new(UNIX_EPOCH + Duration::from_secs(seconds)),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            *self.now.lock().unwrap()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.now.lock().unwrap() += duration;
+        }
+    }
+
+    #[test]
+    fn test_unlock_time() -> Result<()> {
+        assert_eq!(
+            unlock_time(2024, 1)?,
+            UNIX_EPOCH + Duration::from_secs(1733029200)
+        );
+        assert_eq!(
+            unlock_time(2015, 25)?,
+            UNIX_EPOCH + Duration::from_secs(1451019600)
+        );
+        assert!(unlock_time(2024, 26).is_err());
+        assert!(unlock_time(2024, 0).is_err());
+        assert!(unlock_time(2014, 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_until_unlock() -> Result<()> {
+        let clock = FakeClock::at(1733029200 - 90);
+        assert_eq!(
+            time_until_unlock(&clock, 2024, 1)?,
+            Some(Duration::from_secs(90))
+        );
+        clock.sleep(Duration::from_secs(90));
+        assert_eq!(time_until_unlock(&clock, 2024, 1)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_secs(5)), "5s");
+        assert_eq!(format_remaining(Duration::from_millis(4500)), "5s");
+        assert_eq!(format_remaining(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(format_remaining(Duration::from_secs(7503)), "2h 5m 3s");
+        assert_eq!(format_remaining(Duration::from_secs(90061)), "1d 1h 1m 1s");
+    }
+}
@@ -0,0 +1,230 @@
+// Day4 only searches in every direction without wrapping
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::iter;
+
+use super::grid::{Direction, Grid, Pos};
+
+/// Ways a word can be read through the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Directions {
+    Orthogonal,
+    Diagonal,
+    All,
+    /// Steps of `(rows, columns)` between letters, such as `(1, 2)` for a
+    /// knight's move, a zero step is ignored.
+    Custom(Vec<(isize, isize)>),
+}
+
+impl Directions {
+    fn deltas(&self) -> Vec<(isize, isize)> {
+        let deltas = |directions: &[Direction]| directions.iter().map(|d| d.delta()).collect();
+        match self {
+            Directions::Orthogonal => deltas(&Direction::ORTHOGONAL),
+            Directions::Diagonal => deltas(&Direction::DIAGONAL),
+            Directions::All => deltas(&Direction::ALL),
+            Directions::Custom(custom) => custom
+                .iter()
+                .copied()
+                .filter(|delta| *delta != (0, 0))
+                .collect(),
+        }
+    }
+}
+
+/// A word found in the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Match {
+    pub(crate) word: String,
+    pub(crate) start: Pos,
+    /// Step of `(rows, columns)` from one letter to the next
+    pub(crate) delta: (isize, isize),
+}
+
+impl Match {
+    /// Compass direction of the match, `None` for custom steps
+    pub(crate) fn direction(&self) -> Option<Direction> {
+        Direction::from_delta(self.delta)
+    }
+}
+
+/// Looks for a list of words in a grid of letters, in every direction
+/// unless told otherwise.
+#[derive(Debug, Clone)]
+pub(crate) struct WordSearch {
+    words: Vec<Vec<char>>,
+    directions: Directions,
+    wrap_around: bool,
+}
+
+impl WordSearch {
+    pub(crate) fn new<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> WordSearch {
+        WordSearch {
+            words: words
+                .into_iter()
+                .map(|word| word.as_ref().chars().collect::<Vec<char>>())
+                .filter(|word| !word.is_empty())
+                .collect(),
+            directions: Directions::All,
+            wrap_around: false,
+        }
+    }
+
+    pub(crate) fn directions(mut self, directions: Directions) -> WordSearch {
+        self.directions = directions;
+        self
+    }
+
+    /// Lets words run off one edge and carry on from the opposite one, as
+    /// if the grid was drawn on a torus.
+    pub(crate) fn wrap_around(mut self, wrap_around: bool) -> WordSearch {
+        self.wrap_around = wrap_around;
+        self
+    }
+
+    /// Whether `word` reads from `start` in steps of `delta`. When wrapping,
+    /// the path stops once it comes back to `start` so no cell is used twice.
+    fn reads(&self, grid: &Grid<char>, start: Pos, delta: (isize, isize), word: &[char]) -> bool {
+        let step = |pos: &Pos| {
+            let next = match self.wrap_around {
+                true => grid.wrapping_offset(*pos, delta)?,
+                false => grid.offset(*pos, delta)?,
+            };
+            (next != start).then_some(next)
+        };
+        iter::successors(Some(start), step)
+            .take(word.len())
+            .map(|pos| grid[pos])
+            .eq(word.iter().copied())
+    }
+
+    /// Every match, ordered by start row by row, then by word and direction
+    /// in the order they were given. A single letter word is found once per
+    /// cell, with the first direction.
+    pub(crate) fn find_all(&self, grid: &Grid<char>) -> Vec<Match> {
+        let deltas = self.directions.deltas();
+        let mut matches = Vec::new();
+        for start in grid.positions() {
+            for word in self.words.iter().filter(|word| word[0] == grid[start]) {
+                let deltas = match word.len() {
+                    1 => &deltas[..deltas.len().min(1)],
+                    _ => &deltas[..],
+                };
+                for delta in deltas {
+                    if self.reads(grid, start, *delta, word) {
+                        matches.push(Match {
+                            word: word.iter().collect(),
+                            start,
+                            delta: *delta,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    pub(crate) fn count(&self, grid: &Grid<char>) -> usize {
+        self.find_all(grid).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    const GRID: &str = "\
+CATS
+OXAO
+WOLF
+";
+
+    fn found(matches: &[Match]) -> Vec<(&str, Pos, Option<Direction>)> {
+        matches
+            .iter()
+            .map(|m| (m.word.as_str(), m.start, m.direction()))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_all() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        let search = WordSearch::new(["CAT", "COW", "WOLF", "FLOW", "TAL"]);
+        assert_eq!(
+            found(&search.find_all(&grid)),
+            [
+                ("CAT", (0, 0), Some(Direction::East)),
+                ("COW", (0, 0), Some(Direction::South)),
+                ("TAL", (0, 2), Some(Direction::South)),
+                ("WOLF", (2, 0), Some(Direction::East)),
+                ("FLOW", (2, 3), Some(Direction::West)),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_directions() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        let words = ["CAT", "COW", "TAL", "CXL", "SAO"];
+        let count = |directions| WordSearch::new(words).directions(directions).count(&grid);
+        assert_eq!(count(Directions::All), 5);
+        assert_eq!(count(Directions::Orthogonal), 3);
+        assert_eq!(count(Directions::Diagonal), 2);
+        assert_eq!(count(Directions::Custom(vec![(1, 1), (0, 0)])), 1);
+
+        let knight = WordSearch::new(["CA"])
+            .directions(Directions::Custom(vec![(1, 2), (1, -1)]))
+            .find_all(&grid);
+        assert_eq!(found(&knight), [("CA", (0, 0), None)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrap_around() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        let search =
+            WordSearch::new(["SCAT", "FWOL", "TSC", "WCO"]).directions(Directions::Orthogonal);
+        assert_eq!(search.count(&grid), 0);
+        assert_eq!(
+            found(&search.wrap_around(true).find_all(&grid)),
+            [
+                ("TSC", (0, 2), Some(Direction::East)),
+                ("SCAT", (0, 3), Some(Direction::East)),
+                ("WCO", (2, 0), Some(Direction::South)),
+                ("FWOL", (2, 3), Some(Direction::East)),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrap_around_uses_each_cell_once() -> Result<()> {
+        let count = |word: &str, grid: &str| -> Result<usize> {
+            let search = WordSearch::new([word])
+                .directions(Directions::Orthogonal)
+                .wrap_around(true);
+            Ok(search.count(&grid.parse::<Grid<char>>()?))
+        };
+        assert_eq!(count("ABAB", "AB\n")?, 0);
+        assert_eq!(count("AB", "AB\n")?, 2);
+        assert_eq!(count("AA", "A\n")?, 0);
+        assert_eq!(count("CATSC", GRID)?, 0);
+        assert_eq!(count("A", "")?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_letter() -> Result<()> {
+        let grid = GRID.parse::<Grid<char>>()?;
+        assert_eq!(
+            found(&WordSearch::new(["S"]).find_all(&grid)),
+            [("S", (0, 3), Some(Direction::North))]
+        );
+        assert_eq!(WordSearch::new(["A"]).wrap_around(true).count(&grid), 2);
+        let none = Directions::Custom(vec![(0, 0)]);
+        assert_eq!(WordSearch::new(["A"]).directions(none).count(&grid), 0);
+        Ok(())
+    }
+}
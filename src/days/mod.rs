@@ -1,35 +1,164 @@
-use anyhow::Result;
-use serde::Serialize;
-
-use crate::aoc::process_day;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-
-#[derive(
-  clap::ValueEnum, Clone, Default, Debug, Serialize,
-)]
-#[serde(rename_all = "kebab-case")]
-pub enum Days {
-  #[default]
-  /// Day 1: Historian Hysteria
-  Day1,
-  /// Day 2: Red-Nosed Reports
-  Day2,
-  /// Day 3: Mull It Over
-  Day3,
-  /// Day 4: Ceres Search
-  Day4,
-}
-
-pub fn solve(session_id: Option<String>, day: Days) -> Result<()> {
-  match day {
-    Days::Day1 => { let _ = process_day::<day1::Day1>(session_id)?; },
-    Days::Day2 => { let _ = process_day::<day2::Day2>(session_id)?; },
-    Days::Day3 => { let _ = process_day::<day3::Day3>(session_id)?; },
-    Days::Day4 => { let _ = process_day::<day4::Day4>(session_id)?; },
-  }
-  Ok(())
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::aoc::bench::{bench_day, BenchOptions, BenchResult};
+use crate::aoc::client::AocClient;
+use crate::aoc::{process_day, AOCYearDay, Day, DayResult, RunOptions};
+
+/// Registers the solutions of an event, next to plain `mod dayN;` lines so
+/// that rustfmt still reaches the day files.
+///
+/// ```ignore
+/// mod day1;
+/// mod day2;
+///
+/// register_days! {
+///   day1::Day1,
+///   day2::Day2,
+/// }
+/// ```
+///
+/// expands into a `solutions()` function, the id and title of every day come
+/// from its `Day` impl.
+macro_rules! register_days {
+  ($($module:ident :: $day:ident),* $(,)?) => {
+    pub fn solutions() -> Vec<$crate::days::Solution> {
+      vec![$($crate::days::Solution::of::<$module::$day>()),*]
+    }
+  };
+}
+
+/// Collects the solutions of the event modules, each of them using
+/// `register_days!`
+macro_rules! register_years {
+  ($($year:ident),* $(,)?) => {
+    fn all_solutions() -> Vec<Solution> {
+      let mut solutions = Vec::new();
+      $(solutions.extend($year::solutions());)*
+      solutions
+    }
+  };
+}
+
+mod y2024;
+
+register_years! {
+  y2024,
+}
+
+/// A solved puzzle that can be looked up by year and day
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub id: AOCYearDay,
+    pub title: &'static str,
+    pub budget: Option<Duration>,
+    pub solve: fn(&AocClient, &RunOptions) -> Result<DayResult>,
+    pub bench: fn(&AocClient, &BenchOptions) -> Result<BenchResult>,
+}
+
+impl Solution {
+    pub fn of<T>() -> Solution
+    where
+        T: Day + FromStr + Send + Sync + 'static,
+        T::Prepared: Send + Sync + 'static,
+        <T as FromStr>::Err: Send + Sync + 'static,
+        Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
+    {
+        Solution {
+            id: T::id(),
+            title: T::TITLE,
+            budget: T::BUDGET,
+            solve: process_day::<T>,
+            bench: bench_day::<T>,
+        }
+    }
+
+    /// Short label such as `2024/01` used in tables
+    pub fn label(&self) -> String {
+        format!("{}/{:02}", self.id.0, self.id.1)
+    }
+}
+
+/// Every registered solution across all events, ordered by year and day
+pub fn registry() -> Vec<Solution> {
+    let mut solutions = all_solutions();
+    solutions.sort_by_key(|s| s.id);
+    solutions
+}
+
+pub fn latest_year() -> i32 {
+    registry().last().map(|s| s.id.0).unwrap_or_default()
+}
+
+/// Solutions for a single year, or for every year when none is given
+pub fn select(year: Option<i32>) -> Vec<Solution> {
+    registry()
+        .into_iter()
+        .filter(|s| year.is_none_or(|y| s.id.0 == y))
+        .collect()
+}
+
+pub fn find(year: i32, day: i32) -> Result<Solution> {
+    registry()
+        .into_iter()
+        .find(|s| s.id == (year, day))
+        .ok_or(anyhow!("There is no solution for day {} of {}", day, year))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::examples::{example_path, Examples};
+    use crate::aoc::{DayPart, InputSource};
+
+    /// Solves an example file with the registered solution and compares the
+    /// parts that have an expected answer in `data/{year}/{day}.examples.toml`
+    fn check_example(year: i32, day: i32, index: usize) -> Result<()> {
+        let examples = Examples::load(year, day)?;
+        let expected: Vec<(DayPart, &str)> = [DayPart::Part1, DayPart::Part2]
+            .into_iter()
+            .filter_map(|part| Some((part, examples.expected(index, part)?)))
+            .collect();
+        let part = match expected.as_slice() {
+            [] => {
+                return Err(anyhow!(
+                    "Example {} of {}/{} has no expected answers",
+                    index,
+                    year,
+                    day
+                ))
+            }
+            [(part, _)] => Some(*part),
+            _ => None,
+        };
+        let options = RunOptions {
+            part,
+            input: Some(InputSource::File(example_path(year, day, index))),
+            timeout: None,
+        };
+        let result = (find(year, day)?.solve)(&AocClient::new(None), &options)?;
+        for (part, answer) in expected {
+            assert_eq!(result.answer(part), Some(answer), "part {}", part.level());
+        }
+        Ok(())
+    }
+
+    mod examples {
+        use super::check_example;
+
+        include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+    }
+
+    #[test]
+    fn test_registry_lookup() -> Result<()> {
+        let solution = find(2024, 3)?;
+        assert_eq!(solution.id, (2024, 3));
+        assert_eq!(solution.label(), "2024/03");
+        assert!(find(2024, 26).is_err());
+        assert!(select(Some(2015)).is_empty());
+        assert!(select(None).windows(2).all(|w| w[0].id < w[1].id));
+        Ok(())
+    }
 }
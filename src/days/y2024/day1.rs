@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::aoc::parse_error::{parse_at, ParseError};
+use crate::aoc::{input, AOCYearDay, Day};
+
+type LocationIDsList = Vec<i32>;
+type LocationIDsCounts = HashMap<i32, i32>;
+
+#[derive(Debug)]
+pub struct Day1 {
+    list1: LocationIDsList,
+    list2: LocationIDsList,
+}
+
+/// Both lists sorted so that first part can pair them up, along with
+/// how many times each location ID shows up in the second list.
+#[derive(Debug)]
+pub struct SortedLocations {
+    list1: LocationIDsList,
+    list2: LocationIDsList,
+    // In order to reduce number of passes on second list we
+    // collecting details on how many times we see a location ID
+    // from second list in a table so that we can just look it up
+    // for second part.
+    counts: LocationIDsCounts,
+}
+
+impl Day for Day1 {
+    type DayOutputPart1 = i32;
+    type DayOutputPart2 = i32;
+    type Prepared = SortedLocations;
+
+    const TITLE: &'static str = "Day 1: Historian Hysteria";
+
+    fn id() -> AOCYearDay {
+        (2024, 1)
+    }
+
+    fn prepare(&self) -> Result<Self::Prepared> {
+        let mut list1 = self.list1.clone();
+        let mut list2 = self.list2.clone();
+        list1.sort();
+        list2.sort();
+        let mut counts = LocationIDsCounts::new();
+        for l2 in &list2 {
+            *counts.entry(*l2).or_insert(0) += 1;
+        }
+        Ok(SortedLocations {
+            list1,
+            list2,
+            counts,
+        })
+    }
+
+    fn part1(&self, sorted: &Self::Prepared) -> Result<Self::DayOutputPart1> {
+        let distance = sorted
+            .list1
+            .iter()
+            .zip(sorted.list2.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+
+        Ok(distance)
+    }
+
+    fn part2(&self, sorted: &Self::Prepared) -> Result<Self::DayOutputPart2> {
+        let similarity = self
+            .list1
+            .iter()
+            .map(|a| a * sorted.counts.get(a).unwrap_or(&0))
+            .sum();
+        Ok(similarity)
+    }
+}
+
+impl FromStr for Day1 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut list1: Vec<i32> = Vec::new();
+        let mut list2: Vec<i32> = Vec::new();
+
+        for line in input::lines(s) {
+            let entries: Vec<&str> = line.split_whitespace().collect();
+            if entries.len() != 2 {
+                // Point at the first extra entry, or at the end of a short line
+                let at = entries.get(2).copied().unwrap_or(&line[line.len()..]);
+                return Err(ParseError::at_slice(
+                    s,
+                    at,
+                    format!("expected 2 location IDs, got {}", entries.len()),
+                )
+                .into());
+            }
+            let l1 = parse_at::<i32>(s, entries[0])?;
+            list1.push(l1);
+            let l2 = parse_at::<i32>(s, entries[1])?;
+            list2.push(l2);
+        }
+        Ok(Day1 { list1, list2 })
+    }
+}
@@ -3,11 +3,12 @@ use std::str::FromStr;
 use anyhow::Result;
 use log::trace;
 
-use crate::aoc::{AOCYearDay, Day};
+use crate::aoc::parse_error::parse_at;
+use crate::aoc::{input, AOCYearDay, Day};
 
 type Report = Vec<i32>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Day2 {
     reports: Vec<Report>,
 }
@@ -18,7 +19,7 @@ impl Day2 {
 
     fn in_limits(a: i32, b: i32) -> bool {
         let x = (a - b).abs();
-        (Day2::LOWER_LIMIT <= x) && (x <= Day2::UPPER_LIMIT)
+        (Day2::LOWER_LIMIT..=Day2::UPPER_LIMIT).contains(&x)
     }
 
     fn check_report_step_validity(a: i32, b: i32, c: i32) -> bool {
@@ -83,12 +84,19 @@ impl Day2 {
 impl Day for Day2 {
     type DayOutputPart1 = usize;
     type DayOutputPart2 = usize;
+    type Prepared = ();
+
+    const TITLE: &'static str = "Day 2: Red-Nosed Reports";
 
     fn id() -> AOCYearDay {
         (2024, 2)
     }
 
-    fn part1(self) -> Result<Self::DayOutputPart1> {
+    fn prepare(&self) -> Result<Self::Prepared> {
+        Ok(())
+    }
+
+    fn part1(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart1> {
         let safe_reports = self
             .reports
             .iter()
@@ -99,7 +107,7 @@ impl Day for Day2 {
         Ok(safe_reports)
     }
 
-    fn part2(self) -> Result<Self::DayOutputPart2> {
+    fn part2(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart2> {
         let safe_reports = self
             .reports
             .iter()
@@ -116,15 +124,13 @@ impl FromStr for Day2 {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut reports = Vec::<Report>::new();
-        for line in s.split("\n") {
+        for line in input::lines(s) {
             let mut report = Report::new();
-            for entry in line.trim().split_whitespace() {
-                let val = entry.parse::<i32>()?;
+            for entry in line.split_whitespace() {
+                let val = parse_at::<i32>(s, entry)?;
                 report.push(val);
             }
-            if report.len() > 0 {
-                reports.push(report);
-            }
+            reports.push(report);
         }
         Ok(Day2 { reports })
     }
@@ -133,32 +139,6 @@ impl FromStr for Day2 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
-
-    const TEST_INPUT: &str = "\
-        7 6 4 2 1
-        1 2 7 8 9
-        9 7 6 2 1
-        1 3 2 4 5
-        8 6 4 4 1
-        1 3 6 7 9
-    ";
-
-    #[test]
-    fn day2_part1() -> Result<()> {
-        let day = TEST_INPUT.parse::<Day2>()?;
-        let result = day.part1()?;
-        assert_eq!(result, 2);
-        Ok(())
-    }
-
-    #[test]
-    fn day2_part2() -> Result<()> {
-        let day = TEST_INPUT.parse::<Day2>()?;
-        let result = day.part2()?;
-        assert_eq!(result, 4);
-        Ok(())
-    }
 
     // adds '4 7 5 3 1' entry where failure occurs on 7-5 check
     // but removal is needed of '4' instead
@@ -175,26 +155,27 @@ mod tests {
     #[test]
     fn day2_part1_test2() -> Result<()> {
         let day = SECOND_TEST_INPUT.parse::<Day2>()?;
-        let result = day.part1()?;
+        let result = day.part1(&day.prepare()?)?;
         assert_eq!(result, 0);
         Ok(())
     }
 
+    #[test]
+    fn day2_parse_error_location() {
+        let error = "7 6 4\n1 2 x 8\n".parse::<Day2>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: 'x': invalid digit found in string\n\
+             2 | 1 2 x 8\n  \
+               |     ^"
+        );
+    }
+
     #[test]
     fn day2_part2_test2() -> Result<()> {
         let day = SECOND_TEST_INPUT.parse::<Day2>()?;
-        let result = day.part2()?;
+        let result = day.part2(&day.prepare()?)?;
         assert_eq!(result, 5);
         Ok(())
     }
-
-    #[bench]
-    fn bench_part1(b: &mut Bencher) {
-        b.iter(|| TEST_INPUT.parse::<Day2>().unwrap().part1().unwrap());
-    }
-
-    #[bench]
-    fn bench_part2(b: &mut Bencher) {
-        b.iter(|| TEST_INPUT.parse::<Day2>().unwrap().part1().unwrap());
-    }
 }
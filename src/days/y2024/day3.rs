@@ -0,0 +1,97 @@
+use regex::Regex;
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::aoc::parse_error::{parse_at, ParseError};
+use crate::aoc::{AOCYearDay, Day};
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Mul(i32, i32),
+    Do,
+    Dont,
+}
+
+#[derive(Debug)]
+pub struct Day3 {
+    operations: Vec<Operation>,
+}
+
+impl Day for Day3 {
+    type DayOutputPart1 = i32;
+    type DayOutputPart2 = i32;
+    type Prepared = ();
+
+    const TITLE: &'static str = "Day 3: Mull It Over";
+
+    fn id() -> AOCYearDay {
+        (2024, 3)
+    }
+
+    fn prepare(&self) -> Result<Self::Prepared> {
+        Ok(())
+    }
+
+    fn part1(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart1> {
+        Ok(self
+            .operations
+            .iter()
+            .map(|op| {
+                if let Operation::Mul(a, b) = op {
+                    a * b
+                } else {
+                    0
+                }
+            })
+            .sum())
+    }
+
+    fn part2(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart2> {
+        let result = self
+            .operations
+            .iter()
+            .fold((0, true), |(sum, process), op| match op {
+                Operation::Mul(a, b) => {
+                    if process {
+                        (sum + a * b, process)
+                    } else {
+                        (sum, process)
+                    }
+                }
+                Operation::Do => (sum, true),
+                Operation::Dont => (sum, false),
+            });
+        Ok(result.0)
+    }
+}
+
+impl FromStr for Day3 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut operations = Vec::<Operation>::new();
+        let re = Regex::new(r"(mul\((?<a>\d{1,3}),(?<b>\d{1,3})\)|do\(\)|don't\(\))")?;
+        for x in re.find_iter(s) {
+            if x.as_str().starts_with("mul") {
+                if let Some(numbers) = re.captures(x.as_str()) {
+                    let a = parse_at::<i32>(s, &numbers["a"])?;
+                    let b = parse_at::<i32>(s, &numbers["b"])?;
+                    operations.push(Operation::Mul(a, b));
+                }
+            } else if x.as_str() == "do()" {
+                operations.push(Operation::Do);
+            } else if x.as_str() == "don't()" {
+                operations.push(Operation::Dont)
+            } else {
+                return Err(ParseError::at(
+                    s,
+                    x.start(),
+                    format!("unexpected match '{}'", x.as_str()),
+                )
+                .into());
+            }
+        }
+        Ok(Day3 { operations })
+    }
+}
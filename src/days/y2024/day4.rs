@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::aoc::grid::{Grid, Pos};
+use crate::aoc::word_search::{Directions, WordSearch};
+use crate::aoc::{AOCYearDay, Day};
+
+#[derive(Debug)]
+pub struct Day4 {
+    input: Grid<char>,
+}
+
+impl Day for Day4 {
+    type DayOutputPart1 = usize;
+    type DayOutputPart2 = usize;
+    type Prepared = ();
+
+    const TITLE: &'static str = "Day 4: Ceres Search";
+
+    fn id() -> AOCYearDay {
+        (2024, 4)
+    }
+
+    fn prepare(&self) -> Result<Self::Prepared> {
+        Ok(())
+    }
+
+    fn part1(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart1> {
+        Ok(WordSearch::new(["XMAS"]).count(&self.input))
+    }
+
+    fn part2(&self, _prepared: &Self::Prepared) -> Result<Self::DayOutputPart2> {
+        // Every X-MAS is two diagonal MAS sharing their middle letter
+        let mut centres: HashMap<Pos, usize> = HashMap::new();
+        let search = WordSearch::new(["MAS"]).directions(Directions::Diagonal);
+        for found in search.find_all(&self.input) {
+            if let Some(centre) = self.input.offset(found.start, found.delta) {
+                *centres.entry(centre).or_insert(0) += 1;
+            }
+        }
+        Ok(centres.values().filter(|count| **count == 2).count())
+    }
+}
+
+impl FromStr for Day4 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.parse::<Grid<char>>()?;
+        Ok(Day4 { input })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const WIDE_INPUT: &str = "\
+XMASAMXX
+........
+";
+
+    const X_MAS_INPUT: &str = "\
+M.S.X
+.A..M
+M.S.A
+";
+
+    fn solve(input: &str) -> Result<(usize, usize)> {
+        let day = input.parse::<Day4>()?;
+        Ok((day.part1(&day.prepare()?)?, day.part2(&day.prepare()?)?))
+    }
+
+    fn transpose(input: &str) -> Result<String> {
+        let grid = input.parse::<Grid<char>>()?;
+        Ok(Grid::new(grid.cells().t().to_owned()).to_string())
+    }
+
+    #[test]
+    fn day4_non_square() -> Result<()> {
+        assert_eq!(solve(WIDE_INPUT)?, (2, 0));
+        assert_eq!(solve(&transpose(WIDE_INPUT)?)?, (2, 0));
+        assert_eq!(solve(X_MAS_INPUT)?, (0, 1));
+        assert_eq!(solve(&transpose(X_MAS_INPUT)?)?, (0, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn day4_transposed_example_rows() -> Result<()> {
+        let example = fs::read_to_string("./data/2024/4.example1.txt")?;
+        for rows in 1..=example.lines().count() {
+            let cropped = example.lines().take(rows).collect::<Vec<_>>().join("\n");
+            assert_eq!(solve(&cropped)?, solve(&transpose(&cropped)?)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn day4_narrow() -> Result<()> {
+        assert_eq!(solve("X\nM\nA\nS\n")?, (1, 0));
+        assert_eq!(solve("A\n")?, (0, 0));
+        assert_eq!(solve("")?, (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn day4_ragged() {
+        let error = "XMAS\nXMA\nXMAS\n".parse::<Day4>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected 4 cells like the first row, got 3\n\
+             2 | XMA\n  \
+               | ^"
+        );
+    }
+}
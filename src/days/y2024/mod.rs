@@ -0,0 +1,11 @@
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+
+register_days! {
+  day1::Day1,
+  day2::Day2,
+  day3::Day3,
+  day4::Day4,
+}
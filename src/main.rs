@@ -1,10 +1,24 @@
-#![feature(test)]
-extern crate test;
-
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand};
+use log::{error, info};
 
-use anyhow::Result;
-use clap::Parser;
+use aoc::alloc::AllocStats;
+use aoc::answers::{Answers, CheckStatus};
+use aoc::baseline::{parse_threshold, Baseline, CompareStatus, DEFAULT_THRESHOLD};
+use aoc::bench::BenchOptions;
+use aoc::budget::{self, BudgetMode, Spent, DEFAULT_YEAR_BUDGET};
+use aoc::client::{AocClient, DEFAULT_MIN_INTERVAL, DEFAULT_USER_AGENT};
+use aoc::examples::fetch_examples;
+use aoc::ledger::Ledger;
+use aoc::report::{to_csv, to_json, OutputFormat, Record, Table};
+use aoc::submit::{submit_answer, Verdict};
+use aoc::{AOCYearDay, DayPart, DayResult, InputSource, PartResult, RunOptions};
+use days::Solution;
 
 mod aoc;
 mod days;
@@ -13,13 +27,452 @@ mod days;
 #[command(styles = clap::builder::styling::Styles::styled())]
 #[command(version, about, long_about = None)]
 struct Cli {
+    /// Advent of Code event year, defaults to the latest one with solutions
+    #[arg(short, long, global = true)]
+    year: Option<i32>,
+
     /// Advent of Code day to solve
+    #[arg(short, long, default_value_t = 1, global = true, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Solve every day (of --year when given) and print a timing table
+    #[arg(short, long, conflicts_with = "day", global = true)]
+    all: bool,
+
+    /// Only solve this part
+    #[arg(short, long, value_enum)]
+    part: Option<DayPart>,
+
+    /// Read the puzzle input from this file, or from stdin when `-`
+    #[arg(short, long, conflicts_with = "all")]
+    input: Option<String>,
+
+    /// Format for the solved answers and timings
     #[arg(short, long, default_value_t, value_enum)]
-    day: days::Days,
+    output: OutputFormat,
+
+    /// Give up on a part after this many seconds and carry on with the next one
+    #[arg(long, global = true)]
+    timeout: Option<u64>,
+
+    /// What to do when a day or the whole year is slower than its time budget
+    #[arg(long, value_enum, default_value_t, global = true)]
+    budget: BudgetMode,
+
+    /// Time budget in milliseconds for all days of a year together
+    #[arg(long, global = true, default_value_t = DEFAULT_YEAR_BUDGET.as_millis() as u64)]
+    year_budget_ms: u64,
+
+    /// Wait for a locked puzzle to unlock instead of failing
+    #[arg(long, global = true)]
+    wait: bool,
 
     /// Advent of Code Session ID
-    #[arg(long, env)]
+    #[arg(long, env, global = true)]
     aoc_session_id: Option<String>,
+
+    /// User-Agent sent to adventofcode.com, should say how to contact you
+    #[arg(long, env = "AOC_USER_AGENT", global = true, default_value = DEFAULT_USER_AGENT)]
+    user_agent: String,
+
+    /// Minimum number of seconds between two requests to adventofcode.com
+    #[arg(long, global = true, default_value_t = DEFAULT_MIN_INTERVAL.as_secs())]
+    min_request_interval: u64,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the day and submit the answer for one of its parts
+    Submit {
+        /// Part to submit the answer for
+        #[arg(short, long, value_enum)]
+        part: DayPart,
+
+        /// Submit this answer instead of the solved one
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Store the accepted answer of a part solved outside of this tool, without submitting it
+    Record {
+        /// Part the answer was accepted for
+        #[arg(short, long, value_enum)]
+        part: DayPart,
+
+        /// Record this answer instead of the solved one
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Solve every day (of --year when given) and compare the answers with the accepted ones
+    Verify,
+    /// List every registered solution
+    List,
+    /// Save the examples of the day's puzzle page under data/{year}/ for tests
+    FetchExamples,
+    /// Time every step of the day (or of every day with --all) on the real input
+    Bench {
+        /// Number of measured runs
+        #[arg(
+            short = 'n',
+            long,
+            default_value_t = BenchOptions::default().iterations as u32,
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        iterations: u32,
+
+        /// Number of runs before measuring
+        #[arg(long, default_value_t = BenchOptions::default().warmup)]
+        warmup: usize,
+
+        /// Store the medians in data/{year}/baseline.toml
+        #[arg(long)]
+        save_baseline: bool,
+
+        /// Compare the medians with the baseline and fail on regressions
+        #[arg(long)]
+        compare: bool,
+
+        /// Percentage a day may get slower than its baseline before failing
+        #[arg(long, default_value_t = DEFAULT_THRESHOLD, value_parser = parse_threshold)]
+        threshold: f64,
+    },
+}
+
+/// The given answer, or the one the registered solution comes up with
+fn answer_or_solve(
+    client: &AocClient,
+    id: AOCYearDay,
+    part: DayPart,
+    answer: Option<String>,
+) -> Result<String> {
+    if let Some(answer) = answer {
+        return Ok(answer);
+    }
+    let solution = days::find(id.0, id.1)?;
+    let options = RunOptions {
+        part: Some(part),
+        input: None,
+        timeout: None,
+    };
+    let result = (solution.solve)(client, &options)?;
+    Ok(result
+        .answer(part)
+        .ok_or(anyhow!("Part {} has no answer", part.level()))?
+        .to_string())
+}
+
+fn submit(client: &AocClient, id: AOCYearDay, part: DayPart, answer: Option<String>) -> Result<()> {
+    let answer = answer_or_solve(client, id, part, answer)?;
+    let mut ledger = Ledger::load(id)?;
+    ledger.check(part, &answer)?;
+    let verdict = submit_answer(client, id, part, &answer)?;
+    ledger.record(part, &answer, &verdict);
+    ledger.save()?;
+    let (year, day) = id;
+    let mut answers = Answers::load(year)?;
+    let store = match verdict {
+        Verdict::Correct => true,
+        // AoC doesn't say which answer solved it, so one stored before wins
+        Verdict::AlreadySolved => answers.get(day, part).is_none(),
+        _ => false,
+    };
+    if store {
+        answers.store(day, part, &answer);
+        answers.save()?;
+    }
+    info!(
+        "Submitted '{}' for part {}: {}",
+        answer,
+        part.level(),
+        verdict
+    );
+    Ok(())
+}
+
+/// Stores the answer of a part solved without `submit`, e.g. in the browser
+fn record(client: &AocClient, id: AOCYearDay, part: DayPart, answer: Option<String>) -> Result<()> {
+    let answer = answer_or_solve(client, id, part, answer)?;
+    let (year, day) = id;
+    let mut answers = Answers::load(year)?;
+    answers.store(day, part, &answer);
+    answers.save()?;
+    info!("Recorded '{}' for part {}", answer, part.level());
+    Ok(())
+}
+
+fn part_cell(result: &DayResult, part: DayPart, cell: impl Fn(&PartResult) -> String) -> String {
+    result.part(part).map(cell).unwrap_or(String::from("-"))
+}
+
+fn answer_cell(part: &PartResult) -> String {
+    match &part.failure {
+        Some(failure) => failure.to_string(),
+        None => part.answer.clone(),
+    }
+}
+
+/// Time of a step, followed by its heap usage when it was tracked
+fn step_cell(elapsed: Duration, alloc: Option<AllocStats>) -> String {
+    match alloc {
+        Some(alloc) => format!("{:.3?} / {}", elapsed, alloc),
+        None => format!("{:.3?}", elapsed),
+    }
+}
+
+fn timing_table(results: &[(Solution, Result<DayResult>)]) -> Table {
+    let mut table = Table::new(&[
+        "Day", "Parse", "Prepare", "Part 1", "Part 2", "Total", "Answer 1", "Answer 2",
+    ]);
+    let mut total = Duration::ZERO;
+    for (solution, result) in results {
+        match result {
+            Ok(result) => {
+                total += result.total_time();
+                table.add_row(vec![
+                    solution.label(),
+                    step_cell(result.parse_time, result.parse_alloc),
+                    step_cell(result.prepare_time, result.prepare_alloc),
+                    part_cell(result, DayPart::Part1, |p| step_cell(p.elapsed, p.alloc)),
+                    part_cell(result, DayPart::Part2, |p| step_cell(p.elapsed, p.alloc)),
+                    format!("{:.3?}", result.total_time()),
+                    part_cell(result, DayPart::Part1, answer_cell),
+                    part_cell(result, DayPart::Part2, answer_cell),
+                ]);
+            }
+            Err(e) => {
+                table.add_row(vec![
+                    solution.label(),
+                    String::from("-"),
+                    String::from("-"),
+                    String::from("-"),
+                    String::from("-"),
+                    String::from("-"),
+                    format!("ERROR: {}", e),
+                ]);
+            }
+        }
+    }
+    table.add_row(vec![
+        String::from("Total"),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        format!("{:.3?}", total),
+    ]);
+    table
+}
+
+/// How day and year time budgets are checked after solving
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    mode: BudgetMode,
+    year: Duration,
+}
+
+fn spent(solution: &Solution, result: &DayResult) -> Spent {
+    Spent {
+        id: solution.id,
+        budget: solution.budget,
+        time: result.total_time(),
+    }
+}
+
+fn run(
+    client: &AocClient,
+    solutions: Vec<Solution>,
+    options: &RunOptions,
+    output: OutputFormat,
+    budget: Budget,
+) -> Result<()> {
+    let results: Vec<(Solution, Result<DayResult>)> = solutions
+        .into_iter()
+        .map(|solution| (solution, (solution.solve)(client, options)))
+        .collect();
+    let records: Vec<Record> = results
+        .iter()
+        .flat_map(|(solution, result)| Record::from_result(solution.id, result))
+        .collect();
+
+    match output {
+        OutputFormat::Text if results.len() > 1 => println!("{}", timing_table(&results)),
+        OutputFormat::Text => {}
+        OutputFormat::Json => println!("{}", to_json(&records)?),
+        OutputFormat::Csv => print!("{}", to_csv(&records)),
+    }
+
+    let mut failures = 0;
+    let mut spent_times = Vec::new();
+    for (solution, result) in &results {
+        match result {
+            Ok(result) => {
+                if result.failures().next().is_some() {
+                    failures += 1;
+                }
+                spent_times.push(spent(solution, result));
+            }
+            Err(e) => {
+                failures += 1;
+                error!("{} failed: {:#}", solution.label(), e);
+            }
+        }
+    }
+    let within_budget = budget::enforce(budget.mode, &spent_times, budget.year);
+    if failures > 0 {
+        return Err(anyhow!("{} day(s) failed to solve", failures));
+    }
+    within_budget
+}
+
+fn verify(
+    client: &AocClient,
+    solutions: Vec<Solution>,
+    options: &RunOptions,
+    budget: Budget,
+) -> Result<()> {
+    let mut table = Table::new(&["Day", "Part", "Expected", "Actual", "Status"]);
+    let mut answers: HashMap<i32, Answers> = HashMap::new();
+    let mut failures = 0;
+    let mut spent_times = Vec::new();
+    for solution in solutions {
+        let result = match (solution.solve)(client, options) {
+            Ok(result) => result,
+            Err(e) => {
+                failures += 1;
+                table.add_row(vec![
+                    solution.label(),
+                    String::from("-"),
+                    String::from("-"),
+                    e.to_string(),
+                    String::from("ERROR"),
+                ]);
+                continue;
+            }
+        };
+        spent_times.push(spent(&solution, &result));
+        let (year, day_number) = result.id;
+        let answers = match answers.entry(year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Answers::load(year)?),
+        };
+        for part in [DayPart::Part1, DayPart::Part2] {
+            let status = answers.check(&result, part);
+            if status == CheckStatus::Fail {
+                failures += 1;
+            }
+            table.add_row(vec![
+                solution.label(),
+                part.level().to_string(),
+                answers.get(day_number, part).unwrap_or("-").to_string(),
+                part_cell(&result, part, answer_cell),
+                status.to_string(),
+            ]);
+        }
+    }
+    println!("{}", table);
+    let within_budget = budget::enforce(budget.mode, &spent_times, budget.year);
+    if failures > 0 {
+        return Err(anyhow!("{} check(s) failed verification", failures));
+    }
+    within_budget
+}
+
+/// What the bench command does with the baseline after measuring
+#[derive(Debug, Clone, Copy)]
+struct BaselineMode {
+    save: bool,
+    compare: bool,
+    threshold: f64,
+}
+
+fn bench(
+    client: &AocClient,
+    solutions: Vec<Solution>,
+    options: &BenchOptions,
+    mode: BaselineMode,
+) -> Result<()> {
+    let mut table = Table::new(&["Day", "Step", "Min", "Median", "Mean", "Std dev"]);
+    let mut comparisons = Table::new(&["Day", "Baseline", "Current", "Change", "Status"]);
+    let mut baselines: HashMap<i32, Baseline> = HashMap::new();
+    let mut failures = 0;
+    let mut regressions = 0;
+    for solution in solutions {
+        let result = match (solution.bench)(client, options) {
+            Ok(result) => result,
+            Err(e) => {
+                failures += 1;
+                error!("{} failed: {:#}", solution.label(), e);
+                continue;
+            }
+        };
+        for (step, stats) in result.steps() {
+            table.add_row(vec![
+                solution.label(),
+                step.to_string(),
+                format!("{:.3?}", stats.min),
+                format!("{:.3?}", stats.median),
+                format!("{:.3?}", stats.mean),
+                format!("{:.3?}", stats.stddev),
+            ]);
+        }
+        let (year, day) = solution.id;
+        let baseline = match baselines.entry(year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Baseline::load(year)?),
+        };
+        if mode.compare {
+            let comparison = baseline.compare(day, &result, mode.threshold);
+            if comparison.status == CompareStatus::Regressed {
+                regressions += 1;
+            }
+            comparisons.add_row(vec![
+                solution.label(),
+                comparison
+                    .baseline
+                    .map(|b| format!("{:.3?}", b))
+                    .unwrap_or(String::from("-")),
+                format!("{:.3?}", comparison.current),
+                comparison
+                    .change()
+                    .map(|c| format!("{:+.1}%", c))
+                    .unwrap_or(String::from("-")),
+                comparison.status.to_string(),
+            ]);
+        }
+        if mode.save {
+            baseline.store(day, &result);
+        }
+    }
+    println!("{}", table);
+    if mode.compare {
+        println!("{}", comparisons);
+    }
+    if mode.save {
+        for baseline in baselines.values() {
+            baseline.save()?;
+        }
+    }
+    if failures > 0 {
+        return Err(anyhow!("{} day(s) failed to benchmark", failures));
+    }
+    if regressions > 0 {
+        return Err(anyhow!(
+            "{} day(s) got more than {}% slower than the baseline",
+            regressions,
+            mode.threshold
+        ));
+    }
+    Ok(())
+}
+
+fn list() {
+    let mut table = Table::new(&["Day", "Title"]);
+    for solution in days::registry() {
+        table.add_row(vec![solution.label(), solution.title.to_string()]);
+    }
+    println!("{}", table);
 }
 
 fn main() -> Result<()> {
@@ -28,6 +481,113 @@ fn main() -> Result<()> {
     }
     env_logger::init();
     let cli = Cli::parse();
-    days::solve(cli.aoc_session_id, cli.day)?;
+    let client = AocClient::new(cli.aoc_session_id)
+        .user_agent(cli.user_agent)
+        .min_interval(Duration::from_secs(cli.min_request_interval))
+        .wait_for_unlock(cli.wait);
+    let year = cli.year.unwrap_or_else(days::latest_year);
+    let day = i32::from(cli.day);
+    let timeout = cli.timeout.map(Duration::from_secs);
+    let options = RunOptions {
+        part: cli.part,
+        input: cli.input.as_deref().map(InputSource::from),
+        timeout,
+    };
+    let budget = Budget {
+        mode: cli.budget,
+        year: Duration::from_millis(cli.year_budget_ms),
+    };
+    match cli.command {
+        None if cli.all => run(
+            &client,
+            days::select(cli.year),
+            &options,
+            cli.output,
+            budget,
+        )?,
+        None => run(
+            &client,
+            vec![days::find(year, day)?],
+            &options,
+            cli.output,
+            budget,
+        )?,
+        Some(Command::Submit { part, answer }) => {
+            submit(&client, (year, day), part, answer)?;
+        }
+        Some(Command::Record { part, answer }) => {
+            record(&client, (year, day), part, answer)?;
+        }
+        Some(Command::Verify) => {
+            let options = RunOptions {
+                timeout,
+                ..Default::default()
+            };
+            verify(&client, days::select(cli.year), &options, budget)?
+        }
+        Some(Command::Bench {
+            iterations,
+            warmup,
+            save_baseline,
+            compare,
+            threshold,
+        }) => {
+            let solutions = match cli.all {
+                true => days::select(cli.year),
+                false => vec![days::find(year, day)?],
+            };
+            let options = BenchOptions {
+                iterations: iterations as usize,
+                warmup,
+            };
+            let mode = BaselineMode {
+                save: save_baseline,
+                compare,
+                threshold,
+            };
+            bench(&client, solutions, &options, mode)?;
+        }
+        Some(Command::List) => list(),
+        Some(Command::FetchExamples) => {
+            let count = fetch_examples(&client, year, day)?;
+            info!("Found {} example(s) for day {} of {}", count, day, year);
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_day_range() {
+        assert_eq!(Cli::try_parse_from(["aoc", "-d", "4"]).unwrap().day, 4);
+        assert_eq!(Cli::try_parse_from(["aoc", "-d", "25"]).unwrap().day, 25);
+        assert!(Cli::try_parse_from(["aoc", "-d", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "-d", "26"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_record() {
+        let cli = Cli::try_parse_from(["aoc", "record", "-d", "7", "-p", "2", "--answer", "11"]);
+        assert!(matches!(
+            cli.unwrap().command,
+            Some(Command::Record {
+                part: DayPart::Part2,
+                answer: Some(_),
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_bench_all() {
+        let cli = Cli::try_parse_from(["aoc", "bench", "--all", "-n", "1"]).unwrap();
+        assert!(cli.all);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Bench { iterations: 1, .. })
+        ));
+        assert!(Cli::try_parse_from(["aoc", "bench", "-n", "0"]).is_err());
+    }
+}